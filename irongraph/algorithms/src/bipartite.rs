@@ -0,0 +1,255 @@
+//! Module providing bipartiteness checks and one-mode projections of
+//! bipartite graphs.
+//!
+//! The two-coloring is computed with a breadth-first visit of each connected
+//! component, whose first node is always on the left side. When the graph is
+//! not bipartite, the visit tree provides an odd cycle as a witness.
+use crate::utils::{index, neighbours};
+use core::fmt::{Debug, Display};
+use csr::prelude::*;
+use graph::Successors;
+use irontraits::To;
+use rayon::prelude::*;
+use std::collections::VecDeque;
+
+/// One of the two sides of a bipartite graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+impl Side {
+    /// Returns the other side.
+    #[inline(always)]
+    pub fn opposite(self) -> Self {
+        match self {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+}
+
+/// A cycle of odd length, proving that a graph is not bipartite.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OddCycle<N> {
+    /// The nodes of the cycle, where each node has an edge towards the next
+    /// one and the last node has an edge towards the first one.
+    nodes: Vec<N>,
+}
+
+impl<N> OddCycle<N> {
+    /// Returns the nodes of the cycle.
+    #[inline(always)]
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+}
+
+impl<N> Display for OddCycle<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "The graph is not bipartite, as it contains a cycle of odd length {}.",
+            self.nodes.len()
+        )
+    }
+}
+
+impl<N: Debug> std::error::Error for OddCycle<N> {}
+
+/// A two-coloring of a bipartite graph.
+#[derive(Debug, Clone)]
+pub struct Bipartition {
+    /// The side of each node.
+    sides: Vec<Side>,
+}
+
+impl Bipartition {
+    /// Returns the side of each node.
+    #[inline(always)]
+    pub fn sides(&self) -> &[Side] {
+        &self.sides
+    }
+
+    /// Returns the side of the provided node.
+    #[inline(always)]
+    pub fn side<N: To<usize>>(&self, node: N) -> Side {
+        self.sides[index(node)]
+    }
+
+    /// Returns the sorted nodes of the provided side.
+    pub fn nodes<N>(&self, side: Side) -> Vec<N>
+    where
+        usize: To<N>,
+    {
+        self.sides
+            .iter()
+            .enumerate()
+            .filter(|(_, node_side)| **node_side == side)
+            .map(|(node, _)| node.to())
+            .collect()
+    }
+
+    /// Returns the one-mode projection of the graph onto the provided side.
+    ///
+    /// See [`projection`] for the layout of the projected graph.
+    pub fn projection<G>(&self, graph: &G, side: Side) -> VecWeightedCSR<G::Node, usize>
+    where
+        G: Successors + Sync,
+        usize: To<G::Node>,
+    {
+        projection(graph, &self.nodes(side))
+    }
+}
+
+/// Returns a two-coloring of the provided undirected graph.
+///
+/// # Arguments
+/// * `graph`: &G - The undirected graph, with every edge stored in both directions.
+///
+/// # Errors
+/// If the graph is not bipartite, returns one of its odd cycles.
+pub fn bipartition<G>(graph: &G) -> Result<Bipartition, OddCycle<G::Node>>
+where
+    G: Successors,
+    usize: To<G::Node>,
+{
+    let number_of_nodes = graph.number_of_nodes();
+    let mut sides: Vec<Option<Side>> = vec![None; number_of_nodes];
+    let mut parents: Vec<usize> = (0..number_of_nodes).collect();
+    let mut queue = VecDeque::new();
+
+    for root in 0..number_of_nodes {
+        if sides[root].is_some() {
+            continue;
+        }
+        sides[root] = Some(Side::Left);
+        queue.push_back(root);
+        while let Some(src) = queue.pop_front() {
+            let side = sides[src].unwrap();
+            for dst in graph.successors(src.to()) {
+                let dst: usize = dst.to();
+                match sides[dst] {
+                    None => {
+                        sides[dst] = Some(side.opposite());
+                        parents[dst] = src;
+                        queue.push_back(dst);
+                    }
+                    Some(dst_side) if dst_side == side => {
+                        return Err(odd_cycle(&parents, src, dst));
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+    }
+
+    Ok(Bipartition {
+        sides: sides.into_iter().map(Option::unwrap).collect(),
+    })
+}
+
+/// Returns the cycle closed by an edge between two nodes on the same side,
+/// following the visit tree up to their lowest common ancestor.
+fn odd_cycle<N>(parents: &[usize], src: usize, dst: usize) -> OddCycle<N>
+where
+    usize: To<N>,
+{
+    let path_to_root = |mut node: usize| {
+        let mut path = vec![node];
+        while parents[node] != node {
+            node = parents[node];
+            path.push(node);
+        }
+        path
+    };
+    let mut src_path = path_to_root(src);
+    let mut dst_path = path_to_root(dst);
+
+    // Both paths end in the root, so their common suffix leads to the
+    // lowest common ancestor, which is kept only in the source path.
+    while src_path.len() > 1
+        && dst_path.len() > 1
+        && src_path[src_path.len() - 2] == dst_path[dst_path.len() - 2]
+    {
+        src_path.pop();
+        dst_path.pop();
+    }
+    dst_path.pop();
+
+    // From the source up to the ancestor, and then down to the destination.
+    let nodes = src_path
+        .into_iter()
+        .chain(dst_path.into_iter().rev())
+        .map(|node| node.to())
+        .collect();
+    OddCycle { nodes }
+}
+
+/// Returns whether the provided undirected graph is bipartite.
+pub fn is_bipartite<G>(graph: &G) -> bool
+where
+    G: Successors,
+    usize: To<G::Node>,
+{
+    bipartition(graph).is_ok()
+}
+
+/// Returns the one-mode projection of the graph onto the provided nodes,
+/// where two nodes are adjacent when they share at least one neighbour and
+/// the weight of the edge is the number of shared neighbours.
+///
+/// The node `i` of the projection is the node `nodes[i]` of the graph, and
+/// neighbours of neighbours not among the provided nodes are ignored.
+///
+/// # Arguments
+/// * `graph`: &G - The undirected graph, with every edge stored in both directions.
+/// * `nodes`: &[G::Node] - The distinct nodes of the side to project onto.
+pub fn projection<G>(graph: &G, nodes: &[G::Node]) -> VecWeightedCSR<G::Node, usize>
+where
+    G: Successors + Sync,
+    usize: To<G::Node>,
+{
+    let mut projected = vec![usize::MAX; graph.number_of_nodes()];
+    for (position, &node) in nodes.iter().enumerate() {
+        projected[index(node)] = position;
+    }
+
+    let successors: Vec<Vec<(usize, usize)>> = nodes
+        .par_iter()
+        .map_init(
+            || (vec![0; nodes.len()], Vec::new()),
+            |(counts, touched), &src| {
+                let src_position = projected[index(src)];
+                for middle in neighbours(graph, src) {
+                    for dst in neighbours(graph, middle) {
+                        let dst_position = projected[index(dst)];
+                        if dst_position == usize::MAX || dst_position == src_position {
+                            continue;
+                        }
+                        if counts[dst_position] == 0 {
+                            touched.push(dst_position);
+                        }
+                        counts[dst_position] += 1;
+                    }
+                }
+                touched.sort_unstable();
+                touched
+                    .drain(..)
+                    .map(|dst_position| (dst_position, core::mem::take(&mut counts[dst_position])))
+                    .collect()
+            },
+        )
+        .collect();
+
+    let number_of_edges = successors.iter().map(Vec::len).sum();
+    CSRBuilder::default()
+        .number_of_nodes(nodes.len())
+        .number_of_edges(number_of_edges)
+        .sorted()
+        .build_weighted(successors.iter().enumerate().flat_map(|(src, dsts)| {
+            dsts.iter()
+                .map(move |&(dst, weight)| (src.to(), dst.to(), weight))
+        }))
+}
@@ -0,0 +1,356 @@
+//! Module providing exact and approximate betweenness centrality.
+//!
+//! The betweenness is computed with Brandes' algorithm from a set of source
+//! nodes, visited in parallel. Sampling the sources uniformly provides an
+//! unbiased estimate of the betweenness, whose precision depends only on the
+//! number of samples and not on the size of the graph.
+//!
+//! The scores are normalized by `(n - 1)(n - 2)`, the number of ordered pairs
+//! of nodes other than the one being scored, so that they lie in `[0, 1]`
+//! for both directed graphs and undirected graphs storing both directions.
+use crate::utils::{index, neighbours, splitmix64};
+use graph::Successors;
+use irontraits::To;
+use rayon::prelude::*;
+
+/// How the sources of the visits are chosen.
+#[derive(Debug, Clone, Copy)]
+enum Sampling {
+    /// Every node is a source, and the betweenness is exact.
+    Exhaustive,
+    /// The provided number of sources are sampled uniformly with replacement.
+    Sources(usize),
+    /// Sources are sampled until the estimates are within `epsilon` of the
+    /// normalized betweenness with probability at least `1 - delta`.
+    ErrorBound { epsilon: f64, delta: f64 },
+}
+
+/// Parameters of the betweenness centrality.
+#[derive(Debug, Clone)]
+pub struct Betweenness {
+    /// How the sources of the visits are chosen.
+    sampling: Sampling,
+    /// The seed used to sample the sources.
+    seed: u64,
+}
+
+impl Default for Betweenness {
+    fn default() -> Self {
+        Betweenness {
+            sampling: Sampling::Exhaustive,
+            seed: 0x3c6e_f372_fe94_f82b,
+        }
+    }
+}
+
+/// The betweenness centrality of the nodes of a graph.
+#[derive(Debug, Clone)]
+pub struct BetweennessScores {
+    /// The normalized betweenness of each node.
+    scores: Vec<f64>,
+    /// The number of visits performed.
+    number_of_sources: usize,
+}
+
+impl BetweennessScores {
+    /// Returns the normalized betweenness of each node.
+    #[inline(always)]
+    pub fn scores(&self) -> &[f64] {
+        &self.scores
+    }
+
+    /// Returns the normalized betweenness of each node.
+    #[inline(always)]
+    pub fn into_scores(self) -> Vec<f64> {
+        self.scores
+    }
+
+    /// Returns the number of sources visited to compute the scores.
+    #[inline(always)]
+    pub fn number_of_sources(&self) -> usize {
+        self.number_of_sources
+    }
+}
+
+/// The per-node sums of the sampled dependencies and of their squares.
+struct Dependencies {
+    sums: Vec<f64>,
+    squared_sums: Vec<f64>,
+}
+
+impl Dependencies {
+    fn new(number_of_nodes: usize) -> Self {
+        Dependencies {
+            sums: vec![0.0; number_of_nodes],
+            squared_sums: vec![0.0; number_of_nodes],
+        }
+    }
+
+    fn merge(mut self, other: Self) -> Self {
+        for (sum, other) in self.sums.iter_mut().zip(other.sums) {
+            *sum += other;
+        }
+        for (squared_sum, other) in self.squared_sums.iter_mut().zip(other.squared_sums) {
+            *squared_sum += other;
+        }
+        self
+    }
+}
+
+/// The buffers of a single-source visit, reused across the sources.
+struct Visit {
+    distances: Vec<usize>,
+    paths: Vec<f64>,
+    dependencies: Vec<f64>,
+    order: Vec<usize>,
+}
+
+impl Visit {
+    fn new(number_of_nodes: usize) -> Self {
+        Visit {
+            distances: vec![usize::MAX; number_of_nodes],
+            paths: vec![0.0; number_of_nodes],
+            dependencies: vec![0.0; number_of_nodes],
+            order: Vec::new(),
+        }
+    }
+
+    /// Adds the dependencies of the nodes on the source, divided by `n - 2`
+    /// so that they lie in `[0, 1]`, to the provided sums.
+    fn accumulate<G>(&mut self, graph: &G, source: usize, target: &mut Dependencies)
+    where
+        G: Successors,
+        usize: To<G::Node>,
+    {
+        self.distances[source] = 0;
+        self.paths[source] = 1.0;
+        self.order.push(source);
+
+        // The visit order doubles as the queue of the breadth-first visit.
+        let mut position = 0;
+        while position < self.order.len() {
+            let src = self.order[position];
+            position += 1;
+            for dst in neighbours(graph, src.to()) {
+                let dst = index(dst);
+                if self.distances[dst] == usize::MAX {
+                    self.distances[dst] = self.distances[src] + 1;
+                    self.order.push(dst);
+                }
+                if self.distances[dst] == self.distances[src] + 1 {
+                    self.paths[dst] += self.paths[src];
+                }
+            }
+        }
+
+        // The successors on the shortest paths are found again through the
+        // distances, so that the predecessors never need to be stored.
+        let scale = (self.distances.len() - 2) as f64;
+        for &src in self.order.iter().rev() {
+            let mut dependency = 0.0;
+            for dst in neighbours(graph, src.to()) {
+                let dst = index(dst);
+                if self.distances[dst] == self.distances[src] + 1 {
+                    dependency +=
+                        self.paths[src] / self.paths[dst] * (1.0 + self.dependencies[dst]);
+                }
+            }
+            self.dependencies[src] = dependency;
+            if src != source {
+                let dependency = dependency / scale;
+                target.sums[src] += dependency;
+                target.squared_sums[src] += dependency * dependency;
+            }
+        }
+
+        for &node in self.order.iter() {
+            self.distances[node] = usize::MAX;
+            self.paths[node] = 0.0;
+            self.dependencies[node] = 0.0;
+        }
+        self.order.clear();
+    }
+}
+
+impl Betweenness {
+    /// Sets the number of sources to sample, uniformly with replacement.
+    ///
+    /// # Panics
+    /// If the number of sources is zero.
+    pub fn number_of_sources(mut self, number_of_sources: usize) -> Self {
+        assert!(
+            number_of_sources > 0,
+            "The number of sources must be greater than 0."
+        );
+        self.sampling = Sampling::Sources(number_of_sources);
+        self
+    }
+
+    /// Sets the error bound of the estimates, sampling sources until every
+    /// normalized score is within `epsilon` of the exact one with probability
+    /// at least `1 - delta`.
+    ///
+    /// The sources are sampled in rounds of doubling size, stopping as soon
+    /// as the empirical Bernstein bound of every node is below `epsilon`, and
+    /// at the latest when the Hoeffding bound is.
+    ///
+    /// # Arguments
+    /// * `epsilon`: f64 - The maximum absolute error of the normalized scores.
+    /// * `delta`: f64 - The probability of exceeding the maximum error.
+    ///
+    /// # Panics
+    /// If either parameter is not in `(0, 1)`.
+    pub fn error_bound(mut self, epsilon: f64, delta: f64) -> Self {
+        assert!(
+            epsilon > 0.0 && epsilon < 1.0,
+            "The error epsilon must be in (0, 1)."
+        );
+        assert!(
+            delta > 0.0 && delta < 1.0,
+            "The probability delta must be in (0, 1)."
+        );
+        self.sampling = Sampling::ErrorBound { epsilon, delta };
+        self
+    }
+
+    /// Sets the seed used to sample the sources.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Returns the normalized betweenness of the nodes of the provided graph.
+    pub fn scores<G>(&self, graph: &G) -> BetweennessScores
+    where
+        G: Successors + Sync,
+        usize: To<G::Node>,
+    {
+        let number_of_nodes = graph.number_of_nodes();
+        if number_of_nodes < 3 {
+            return BetweennessScores {
+                scores: vec![0.0; number_of_nodes],
+                number_of_sources: 0,
+            };
+        }
+
+        let (dependencies, number_of_sources) = match self.sampling {
+            Sampling::Exhaustive => (
+                self.dependencies(graph, (0..number_of_nodes).into_par_iter()),
+                number_of_nodes,
+            ),
+            Sampling::Sources(number_of_sources) => (
+                self.dependencies(graph, self.sources(number_of_nodes, 0..number_of_sources)),
+                number_of_sources,
+            ),
+            Sampling::ErrorBound { epsilon, delta } => {
+                self.adaptive_dependencies(graph, epsilon, delta)
+            }
+        };
+
+        // The mean dependency over the sources, rescaled from `n - 2` to
+        // the `(n - 1)(n - 2)` pairs averaged over the `n` possible sources.
+        let scale = number_of_nodes as f64 / ((number_of_nodes - 1) * number_of_sources) as f64;
+        BetweennessScores {
+            scores: dependencies
+                .sums
+                .into_iter()
+                .map(|sum| sum * scale)
+                .collect(),
+            number_of_sources,
+        }
+    }
+
+    /// Returns the sources sampled with the provided indices.
+    fn sources(
+        &self,
+        number_of_nodes: usize,
+        indices: core::ops::Range<usize>,
+    ) -> impl IndexedParallelIterator<Item = usize> + '_ {
+        indices.into_par_iter().map(move |sample| {
+            (splitmix64(self.seed.wrapping_add(sample as u64)) % number_of_nodes as u64) as usize
+        })
+    }
+
+    fn dependencies<G>(
+        &self,
+        graph: &G,
+        sources: impl ParallelIterator<Item = usize>,
+    ) -> Dependencies
+    where
+        G: Successors + Sync,
+        usize: To<G::Node>,
+    {
+        let number_of_nodes = graph.number_of_nodes();
+        sources
+            .fold(
+                || {
+                    (
+                        Visit::new(number_of_nodes),
+                        Dependencies::new(number_of_nodes),
+                    )
+                },
+                |(mut visit, mut dependencies), source| {
+                    visit.accumulate(graph, source, &mut dependencies);
+                    (visit, dependencies)
+                },
+            )
+            .map(|(_, dependencies)| dependencies)
+            .reduce(|| Dependencies::new(number_of_nodes), Dependencies::merge)
+    }
+
+    /// Samples sources in rounds of doubling size until the error bound holds.
+    fn adaptive_dependencies<G>(&self, graph: &G, epsilon: f64, delta: f64) -> (Dependencies, usize)
+    where
+        G: Successors + Sync,
+        usize: To<G::Node>,
+    {
+        let number_of_nodes = graph.number_of_nodes();
+        // The normalized scores are the mean dependencies times n / (n - 1).
+        let epsilon = epsilon * (number_of_nodes - 1) as f64 / number_of_nodes as f64;
+
+        // Hoeffding's bound with a union bound over the nodes.
+        let maximum_sources = ((2.0 * number_of_nodes as f64 / delta).ln()
+            / (2.0 * epsilon * epsilon))
+            .ceil() as usize;
+        let initial_sources = ((2.0 / delta).ln() / epsilon)
+            .ceil()
+            .min(maximum_sources as f64) as usize;
+        let number_of_rounds = (maximum_sources as f64 / initial_sources as f64)
+            .log2()
+            .ceil() as usize
+            + 1;
+        // The empirical Bernstein bound must hold for every node in every round.
+        let log_term = (2.0 * (number_of_nodes * number_of_rounds) as f64 / delta).ln();
+
+        let mut dependencies = Dependencies::new(number_of_nodes);
+        let mut number_of_sources = 0;
+        let mut target_sources = initial_sources;
+        loop {
+            dependencies = dependencies.merge(self.dependencies(
+                graph,
+                self.sources(number_of_nodes, number_of_sources..target_sources),
+            ));
+            number_of_sources = target_sources;
+            if number_of_sources >= maximum_sources {
+                break;
+            }
+
+            let samples = number_of_sources as f64;
+            let largest_radius = dependencies
+                .sums
+                .par_iter()
+                .zip(dependencies.squared_sums.par_iter())
+                .map(|(&sum, &squared_sum)| {
+                    let variance = ((squared_sum - sum * sum / samples) / (samples - 1.0)).max(0.0);
+                    (2.0 * variance * log_term / samples).sqrt()
+                        + 7.0 * log_term / (3.0 * (samples - 1.0))
+                })
+                .reduce(|| 0.0, f64::max);
+            if largest_radius <= epsilon {
+                break;
+            }
+            target_sources = (2 * number_of_sources).min(maximum_sources);
+        }
+        (dependencies, number_of_sources)
+    }
+}
@@ -0,0 +1,636 @@
+//! Module providing community detection by label propagation and by the Louvain method.
+//!
+//! Both algorithms expect undirected graphs, and use the stored adjacency
+//! matrix as the weights of the graph: the degree of a node is the sum of the
+//! weights of its successors, and the total weight is the sum of all the
+//! weights, so that every undirected edge is counted twice. The modularity of
+//! a partition at resolution γ is then the sum over its communities of the
+//! fraction of the total weight within the community, minus γ times the
+//! squared fraction of the total weight of the degrees in the community.
+//!
+//! To be reproducible regardless of the number of threads, the nodes are
+//! updated in synchronous rounds: in every sweep the nodes are split in two
+//! halves by a seeded coin, and the nodes of each half all choose their new
+//! community looking at the assignment left by the previous half.
+use crate::utils::splitmix64;
+use graph::{Successors, WeightedSuccessors};
+use irontraits::To;
+use rayon::prelude::*;
+
+/// The communities found by a community detection algorithm.
+#[derive(Debug, Clone)]
+pub struct Communities {
+    /// The community of each node, from zero to the number of communities.
+    communities: Vec<usize>,
+    /// The number of distinct communities.
+    number_of_communities: usize,
+    /// The modularity of the partition.
+    modularity: f64,
+}
+
+impl Communities {
+    /// Returns the community of each node.
+    #[inline(always)]
+    pub fn communities(&self) -> &[usize] {
+        &self.communities
+    }
+
+    /// Returns the number of communities.
+    #[inline(always)]
+    pub fn number_of_communities(&self) -> usize {
+        self.number_of_communities
+    }
+
+    /// Returns the modularity of the partition.
+    #[inline(always)]
+    pub fn modularity(&self) -> f64 {
+        self.modularity
+    }
+
+    /// Returns the number of nodes in each community.
+    pub fn community_sizes(&self) -> Vec<usize> {
+        let mut sizes = vec![0; self.number_of_communities];
+        for &community in self.communities.iter() {
+            sizes[community] += 1;
+        }
+        sizes
+    }
+}
+
+/// Graphs whose edges can be visited alongside a weight.
+trait EdgeWeights: Sync {
+    fn number_of_nodes(&self) -> usize;
+
+    fn for_each_neighbour<F: FnMut(usize, f64)>(&self, node: usize, callback: F);
+}
+
+struct Unweighted<'a, G>(&'a G);
+
+impl<G> EdgeWeights for Unweighted<'_, G>
+where
+    G: Successors + Sync,
+    usize: To<G::Node>,
+{
+    #[inline(always)]
+    fn number_of_nodes(&self) -> usize {
+        self.0.number_of_nodes()
+    }
+
+    #[inline(always)]
+    fn for_each_neighbour<F: FnMut(usize, f64)>(&self, node: usize, mut callback: F) {
+        let node: G::Node = node.to();
+        for dst in self.0.successors(node) {
+            callback(dst.to(), 1.0);
+        }
+    }
+}
+
+struct Weighted<'a, G>(&'a G);
+
+impl<G> EdgeWeights for Weighted<'_, G>
+where
+    G: WeightedSuccessors + Sync,
+    usize: To<G::Node>,
+    G::Weight: To<f64>,
+{
+    #[inline(always)]
+    fn number_of_nodes(&self) -> usize {
+        self.0.number_of_nodes()
+    }
+
+    #[inline(always)]
+    fn for_each_neighbour<F: FnMut(usize, f64)>(&self, node: usize, mut callback: F) {
+        let node: G::Node = node.to();
+        for (dst, weight) in self.0.weighted_successors(node) {
+            callback(dst.to(), weight.to());
+        }
+    }
+}
+
+/// The weighted graph of the communities, built between the levels of the Louvain method.
+struct Adjacency {
+    offsets: Vec<usize>,
+    destinations: Vec<usize>,
+    weights: Vec<f64>,
+}
+
+impl EdgeWeights for Adjacency {
+    #[inline(always)]
+    fn number_of_nodes(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    #[inline(always)]
+    fn for_each_neighbour<F: FnMut(usize, f64)>(&self, node: usize, mut callback: F) {
+        let (start, end) = (self.offsets[node], self.offsets[node + 1]);
+        for (&dst, &weight) in self.destinations[start..end]
+            .iter()
+            .zip(self.weights[start..end].iter())
+        {
+            callback(dst, weight);
+        }
+    }
+}
+
+/// Returns the seeded coin used to split the nodes in two halves.
+#[inline(always)]
+fn coin(seed: u64, sweep: usize, node: usize) -> bool {
+    splitmix64(seed ^ splitmix64(sweep as u64 ^ splitmix64(node as u64))) & 1 == 1
+}
+
+/// Sorts the pairs by community, and sums the weights of equal communities.
+fn merge_by_community(buffer: &mut Vec<(usize, f64)>) {
+    buffer.sort_unstable_by_key(|(community, _)| *community);
+    let mut length = 0;
+    for i in 0..buffer.len() {
+        if length > 0 && buffer[length - 1].0 == buffer[i].0 {
+            buffer[length - 1].1 += buffer[i].1;
+        } else {
+            buffer[length] = buffer[i];
+            length += 1;
+        }
+    }
+    buffer.truncate(length);
+}
+
+/// Writes in the buffer the weight from the node towards each adjacent
+/// community, self-loops excluded, sorted by community.
+#[inline(always)]
+fn community_weights<W: EdgeWeights>(
+    graph: &W,
+    node: usize,
+    communities: &[usize],
+    buffer: &mut Vec<(usize, f64)>,
+) {
+    buffer.clear();
+    graph.for_each_neighbour(node, |dst, weight| {
+        if dst != node {
+            buffer.push((communities[dst], weight));
+        }
+    });
+    merge_by_community(buffer);
+}
+
+/// Relabels the communities from zero in order of first appearance, and
+/// returns their number.
+fn compact(communities: &mut [usize]) -> usize {
+    let mut labels = vec![usize::MAX; communities.len()];
+    let mut number_of_communities = 0;
+    for community in communities.iter_mut() {
+        if labels[*community] == usize::MAX {
+            labels[*community] = number_of_communities;
+            number_of_communities += 1;
+        }
+        *community = labels[*community];
+    }
+    number_of_communities
+}
+
+fn degrees<W: EdgeWeights>(graph: &W) -> Vec<f64> {
+    (0..graph.number_of_nodes())
+        .into_par_iter()
+        .map(|node| {
+            let mut degree = 0.0;
+            graph.for_each_neighbour(node, |_, weight| degree += weight);
+            degree
+        })
+        .collect()
+}
+
+fn partition_modularity<W: EdgeWeights>(
+    graph: &W,
+    communities: &[usize],
+    degrees: &[f64],
+    resolution: f64,
+) -> f64 {
+    let total_weight: f64 = degrees.par_iter().sum();
+    if total_weight == 0.0 {
+        return 0.0;
+    }
+
+    let internal_weight: f64 = (0..graph.number_of_nodes())
+        .into_par_iter()
+        .map(|node| {
+            let mut weight = 0.0;
+            graph.for_each_neighbour(node, |dst, edge_weight| {
+                if communities[dst] == communities[node] {
+                    weight += edge_weight;
+                }
+            });
+            weight
+        })
+        .sum();
+
+    let mut community_degrees = vec![0.0; communities.iter().copied().max().map_or(0, |c| c + 1)];
+    for (&community, &degree) in communities.iter().zip(degrees.iter()) {
+        community_degrees[community] += degree;
+    }
+
+    internal_weight / total_weight
+        - resolution
+            * community_degrees
+                .iter()
+                .map(|degree| (degree / total_weight).powi(2))
+                .sum::<f64>()
+}
+
+/// Returns the modularity of the provided partition of an undirected graph.
+///
+/// # Arguments
+/// * `graph`: &G - The graph, with both directions of every edge.
+/// * `communities`: &[usize] - The community of each node.
+pub fn modularity<G>(graph: &G, communities: &[usize]) -> f64
+where
+    G: Successors + Sync,
+    usize: To<G::Node>,
+{
+    let graph = Unweighted(graph);
+    partition_modularity(&graph, communities, &degrees(&graph), 1.0)
+}
+
+/// Returns the modularity of the provided partition of a weighted undirected graph.
+///
+/// # Arguments
+/// * `graph`: &G - The graph, with both directions of every edge.
+/// * `communities`: &[usize] - The community of each node.
+pub fn weighted_modularity<G>(graph: &G, communities: &[usize]) -> f64
+where
+    G: WeightedSuccessors + Sync,
+    usize: To<G::Node>,
+    G::Weight: To<f64>,
+{
+    let graph = Weighted(graph);
+    partition_modularity(&graph, communities, &degrees(&graph), 1.0)
+}
+
+/// Parameters of the label propagation.
+#[derive(Debug, Clone)]
+pub struct LabelPropagation {
+    /// The maximum number of sweeps over the nodes.
+    maximum_iterations: usize,
+    /// The seed used to split the nodes and to break ties.
+    seed: u64,
+}
+
+impl Default for LabelPropagation {
+    fn default() -> Self {
+        LabelPropagation {
+            maximum_iterations: 100,
+            seed: 0x4342_e304_55cc_e670,
+        }
+    }
+}
+
+impl LabelPropagation {
+    /// Sets the maximum number of sweeps over the nodes.
+    pub fn maximum_iterations(mut self, maximum_iterations: usize) -> Self {
+        self.maximum_iterations = maximum_iterations;
+        self
+    }
+
+    /// Sets the seed used to split the nodes and to break ties.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Returns the communities of the provided undirected graph.
+    pub fn communities<G>(&self, graph: &G) -> Communities
+    where
+        G: Successors + Sync,
+        usize: To<G::Node>,
+    {
+        self.run(&Unweighted(graph))
+    }
+
+    /// Returns the communities of the provided weighted undirected graph.
+    pub fn weighted_communities<G>(&self, graph: &G) -> Communities
+    where
+        G: WeightedSuccessors + Sync,
+        usize: To<G::Node>,
+        G::Weight: To<f64>,
+    {
+        self.run(&Weighted(graph))
+    }
+
+    fn run<W: EdgeWeights>(&self, graph: &W) -> Communities {
+        let mut labels: Vec<usize> = (0..graph.number_of_nodes()).collect();
+
+        for iteration in 0..self.maximum_iterations {
+            let tie_seed = splitmix64(self.seed ^ iteration as u64);
+            let mut changed = false;
+            for half in [false, true] {
+                let updates: Vec<(usize, usize)> = (0..graph.number_of_nodes())
+                    .into_par_iter()
+                    .filter(|&node| coin(self.seed, iteration, node) == half)
+                    .map_init(Vec::new, |buffer, node| {
+                        community_weights(graph, node, &labels, buffer);
+                        let current = labels[node];
+                        let mut best = current;
+                        let mut best_weight = buffer
+                            .iter()
+                            .find(|(label, _)| *label == current)
+                            .map_or(0.0, |(_, weight)| *weight);
+                        // The current label is kept on ties, while the ties
+                        // between the other labels are broken by a seeded hash.
+                        for &(label, weight) in buffer.iter() {
+                            if weight > best_weight
+                                || (weight == best_weight
+                                    && best != current
+                                    && splitmix64(tie_seed ^ label as u64)
+                                        < splitmix64(tie_seed ^ best as u64))
+                            {
+                                best = label;
+                                best_weight = weight;
+                            }
+                        }
+                        (node, best)
+                    })
+                    .filter(|&(node, label)| labels[node] != label)
+                    .collect();
+
+                changed |= !updates.is_empty();
+                for (node, label) in updates {
+                    labels[node] = label;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let number_of_communities = compact(&mut labels);
+        let modularity = partition_modularity(graph, &labels, &degrees(graph), 1.0);
+        Communities {
+            communities: labels,
+            number_of_communities,
+            modularity,
+        }
+    }
+}
+
+/// Parameters of the Louvain method.
+#[derive(Debug, Clone)]
+pub struct Louvain {
+    /// The resolution of the modularity, the higher the smaller the communities.
+    resolution: f64,
+    /// The minimum modularity gain of a sweep to keep moving the nodes.
+    tolerance: f64,
+    /// The maximum number of sweeps over the nodes in each level.
+    maximum_sweeps: usize,
+    /// The maximum number of levels, i.e. of aggregations of the communities.
+    maximum_levels: usize,
+    /// The seed used to split the nodes.
+    seed: u64,
+}
+
+impl Default for Louvain {
+    fn default() -> Self {
+        Louvain {
+            resolution: 1.0,
+            tolerance: 1e-6,
+            maximum_sweeps: 100,
+            maximum_levels: 100,
+            seed: 0x7842_33b8_e740_9b54,
+        }
+    }
+}
+
+impl Louvain {
+    /// Sets the resolution of the modularity, the higher the smaller the communities.
+    ///
+    /// # Panics
+    /// If the resolution is not strictly positive.
+    pub fn resolution(mut self, resolution: f64) -> Self {
+        assert!(resolution > 0.0, "The resolution must be positive.");
+        self.resolution = resolution;
+        self
+    }
+
+    /// Sets the minimum modularity gain of a sweep to keep moving the nodes.
+    pub fn tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Sets the maximum number of sweeps over the nodes in each level.
+    pub fn maximum_sweeps(mut self, maximum_sweeps: usize) -> Self {
+        self.maximum_sweeps = maximum_sweeps;
+        self
+    }
+
+    /// Sets the maximum number of levels.
+    pub fn maximum_levels(mut self, maximum_levels: usize) -> Self {
+        self.maximum_levels = maximum_levels;
+        self
+    }
+
+    /// Sets the seed used to split the nodes.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Returns the communities of the provided undirected graph.
+    ///
+    /// The modularity of the returned communities is computed at the
+    /// configured resolution.
+    pub fn communities<G>(&self, graph: &G) -> Communities
+    where
+        G: Successors + Sync,
+        usize: To<G::Node>,
+    {
+        self.run(&Unweighted(graph))
+    }
+
+    /// Returns the communities of the provided weighted undirected graph.
+    ///
+    /// The modularity of the returned communities is computed at the
+    /// configured resolution.
+    pub fn weighted_communities<G>(&self, graph: &G) -> Communities
+    where
+        G: WeightedSuccessors + Sync,
+        usize: To<G::Node>,
+        G::Weight: To<f64>,
+    {
+        self.run(&Weighted(graph))
+    }
+
+    fn run<W: EdgeWeights>(&self, graph: &W) -> Communities {
+        let mut assignment: Vec<usize> = (0..graph.number_of_nodes()).collect();
+        let mut number_of_communities = assignment.len();
+        let mut aggregated: Option<Adjacency> = None;
+
+        for level in 0..self.maximum_levels {
+            let (communities, level_communities) = match aggregated.as_ref() {
+                None => self.local_moving(graph, level),
+                Some(aggregated) => self.local_moving(aggregated, level),
+            };
+
+            assignment
+                .par_iter_mut()
+                .for_each(|community| *community = communities[*community]);
+            let merged = level_communities < number_of_communities;
+            number_of_communities = level_communities;
+            if !merged {
+                break;
+            }
+
+            aggregated = Some(match aggregated.as_ref() {
+                None => aggregate(graph, &communities, level_communities),
+                Some(aggregated) => aggregate(aggregated, &communities, level_communities),
+            });
+        }
+
+        let modularity = partition_modularity(graph, &assignment, &degrees(graph), self.resolution);
+        Communities {
+            communities: assignment,
+            number_of_communities,
+            modularity,
+        }
+    }
+
+    /// Moves the nodes between the communities while the modularity grows,
+    /// and returns the compacted communities alongside their number.
+    fn local_moving<W: EdgeWeights>(&self, graph: &W, level: usize) -> (Vec<usize>, usize) {
+        let seed = splitmix64(self.seed ^ level as u64);
+        let degrees = degrees(graph);
+        let total_weight: f64 = degrees.par_iter().sum();
+        let mut communities: Vec<usize> = (0..graph.number_of_nodes()).collect();
+
+        if total_weight > 0.0 {
+            let mut community_degrees = degrees.clone();
+            let mut community_sizes = vec![1_usize; communities.len()];
+            let mut modularity =
+                partition_modularity(graph, &communities, &degrees, self.resolution);
+
+            for sweep in 0..self.maximum_sweeps {
+                let previous_communities = communities.clone();
+
+                for half in [false, true] {
+                    let moves: Vec<(usize, usize)> = (0..graph.number_of_nodes())
+                        .into_par_iter()
+                        .filter(|&node| coin(seed, sweep, node) == half)
+                        .map_init(Vec::new, |buffer, node| {
+                            community_weights(graph, node, &communities, buffer);
+                            let current = communities[node];
+                            let degree = degrees[node];
+                            // The modularity gain of moving the node, once
+                            // isolated, into the community, up to a constant factor.
+                            let gain = |community: usize, weight: f64| {
+                                let community_degree = community_degrees[community]
+                                    - if community == current { degree } else { 0.0 };
+                                weight - self.resolution * degree * community_degree / total_weight
+                            };
+                            let mut best = current;
+                            let mut best_gain = gain(
+                                current,
+                                buffer
+                                    .iter()
+                                    .find(|(community, _)| *community == current)
+                                    .map_or(0.0, |(_, weight)| *weight),
+                            );
+                            for &(community, weight) in buffer.iter() {
+                                // A singleton only joins singletons with a smaller
+                                // id, or two of them could swap their communities.
+                                if community_sizes[current] == 1
+                                    && community_sizes[community] == 1
+                                    && community > current
+                                {
+                                    continue;
+                                }
+                                let community_gain = gain(community, weight);
+                                if community_gain > best_gain {
+                                    best = community;
+                                    best_gain = community_gain;
+                                }
+                            }
+                            (node, best)
+                        })
+                        .filter(|&(node, community)| communities[node] != community)
+                        .collect();
+
+                    for (node, community) in moves {
+                        community_degrees[communities[node]] -= degrees[node];
+                        community_degrees[community] += degrees[node];
+                        community_sizes[communities[node]] -= 1;
+                        community_sizes[community] += 1;
+                        communities[node] = community;
+                    }
+                }
+
+                // The synchronous moves may lower the modularity, in which
+                // case the sweep is reverted.
+                let new_modularity =
+                    partition_modularity(graph, &communities, &degrees, self.resolution);
+                if new_modularity < modularity {
+                    communities = previous_communities;
+                    break;
+                }
+                let modularity_gain = new_modularity - modularity;
+                modularity = new_modularity;
+                if modularity_gain <= self.tolerance {
+                    break;
+                }
+            }
+        }
+
+        let number_of_communities = compact(&mut communities);
+        (communities, number_of_communities)
+    }
+}
+
+/// Returns the graph whose nodes are the provided communities, where the
+/// weight between two communities is the sum of the weights between their
+/// nodes, and the weight within a community becomes a self-loop.
+fn aggregate<W: EdgeWeights>(
+    graph: &W,
+    communities: &[usize],
+    number_of_communities: usize,
+) -> Adjacency {
+    let mut members_offsets = vec![0; number_of_communities + 1];
+    for &community in communities.iter() {
+        members_offsets[community + 1] += 1;
+    }
+    for community in 0..number_of_communities {
+        members_offsets[community + 1] += members_offsets[community];
+    }
+    let mut members = vec![0; communities.len()];
+    let mut positions = members_offsets.clone();
+    for (node, &community) in communities.iter().enumerate() {
+        members[positions[community]] = node;
+        positions[community] += 1;
+    }
+
+    let rows: Vec<Vec<(usize, f64)>> = (0..number_of_communities)
+        .into_par_iter()
+        .map(|community| {
+            let mut row = Vec::new();
+            for &node in &members[members_offsets[community]..members_offsets[community + 1]] {
+                graph.for_each_neighbour(node, |dst, weight| {
+                    row.push((communities[dst], weight));
+                });
+            }
+            merge_by_community(&mut row);
+            row
+        })
+        .collect();
+
+    let mut offsets = Vec::with_capacity(number_of_communities + 1);
+    offsets.push(0);
+    for row in rows.iter() {
+        offsets.push(offsets[offsets.len() - 1] + row.len());
+    }
+
+    Adjacency {
+        offsets,
+        destinations: rows
+            .par_iter()
+            .flat_map_iter(|row| row.iter().map(|(dst, _)| *dst))
+            .collect(),
+        weights: rows
+            .par_iter()
+            .flat_map_iter(|row| row.iter().map(|(_, weight)| *weight))
+            .collect(),
+    }
+}
@@ -0,0 +1,206 @@
+//! Module providing the k-core decomposition and the degeneracy ordering.
+//!
+//! The sequential variant is the bucket-based algorithm by Batagelj and
+//! Zaversnik, which runs in linear time. The parallel variant peels, for each
+//! k, all the nodes with degree at most k in synchronous rounds, which is
+//! preferable on large CSRs with a small degeneracy.
+use crate::utils::neighbours;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use graph::Successors;
+use irontraits::To;
+use rayon::prelude::*;
+
+/// The core numbers of an undirected graph, alongside a degeneracy ordering.
+#[derive(Debug, Clone)]
+pub struct CoreDecomposition<N> {
+    /// The core number of each node.
+    core_numbers: Vec<usize>,
+    /// The nodes in the order in which they were removed.
+    ordering: Vec<N>,
+}
+
+impl<N: Copy> CoreDecomposition<N> {
+    /// Returns the core number of each node.
+    #[inline(always)]
+    pub fn core_numbers(&self) -> &[usize] {
+        &self.core_numbers
+    }
+
+    /// Returns the degeneracy of the graph, i.e. its maximum core number.
+    pub fn degeneracy(&self) -> usize {
+        self.core_numbers.iter().copied().max().unwrap_or(0)
+    }
+
+    /// Returns the degeneracy ordering of the nodes.
+    ///
+    /// Every node has at most `degeneracy()` neighbours that follow it in
+    /// the ordering.
+    #[inline(always)]
+    pub fn degeneracy_ordering(&self) -> &[N] {
+        &self.ordering
+    }
+
+    /// Returns the nodes of the k-core, i.e. the nodes whose core number is at least k.
+    pub fn k_core(&self, k: usize) -> Vec<N>
+    where
+        usize: To<N>,
+    {
+        self.core_numbers
+            .iter()
+            .enumerate()
+            .filter(|(_, &core_number)| core_number >= k)
+            .map(|(node, _)| node.to())
+            .collect()
+    }
+}
+
+/// Computes the k-core decomposition of the provided undirected graph.
+///
+/// # Arguments
+/// * `graph`: &G - The graph, with both directions of every edge and sorted successors.
+pub fn core_decomposition<G>(graph: &G) -> CoreDecomposition<G::Node>
+where
+    G: Successors,
+    usize: To<G::Node>,
+{
+    let number_of_nodes = graph.number_of_nodes();
+
+    let mut degrees: Vec<usize> = (0..number_of_nodes)
+        .map(|node| neighbours(graph, node.to()).count())
+        .collect();
+    let maximum_degree = degrees.iter().copied().max().unwrap_or(0);
+
+    // The start of the bucket of each degree, in the array of the nodes sorted by degree.
+    let mut bins = vec![0; maximum_degree + 1];
+    for &degree in degrees.iter() {
+        bins[degree] += 1;
+    }
+    let mut start = 0;
+    for bin in bins.iter_mut() {
+        let size = *bin;
+        *bin = start;
+        start += size;
+    }
+
+    let mut positions = vec![0; number_of_nodes];
+    let mut sorted_nodes = vec![0; number_of_nodes];
+    for node in 0..number_of_nodes {
+        positions[node] = bins[degrees[node]];
+        sorted_nodes[positions[node]] = node;
+        bins[degrees[node]] += 1;
+    }
+    for degree in (1..=maximum_degree).rev() {
+        bins[degree] = bins[degree - 1];
+    }
+    bins[0] = 0;
+
+    for i in 0..number_of_nodes {
+        let node = sorted_nodes[i];
+        for neighbour in neighbours(graph, node.to()) {
+            let neighbour: usize = neighbour.to();
+            if degrees[neighbour] > degrees[node] {
+                // We move the neighbour to the start of its bucket, and then
+                // shrink the bucket so that it falls into the previous one.
+                let neighbour_degree = degrees[neighbour];
+                let neighbour_position = positions[neighbour];
+                let first_position = bins[neighbour_degree];
+                let first_node = sorted_nodes[first_position];
+                if neighbour != first_node {
+                    positions[neighbour] = first_position;
+                    positions[first_node] = neighbour_position;
+                    sorted_nodes[neighbour_position] = first_node;
+                    sorted_nodes[first_position] = neighbour;
+                }
+                bins[neighbour_degree] += 1;
+                degrees[neighbour] -= 1;
+            }
+        }
+    }
+
+    CoreDecomposition {
+        core_numbers: degrees,
+        ordering: sorted_nodes.into_iter().map(|node| node.to()).collect(),
+    }
+}
+
+/// Computes in parallel the k-core decomposition of the provided undirected graph.
+///
+/// The nodes removed in the same round appear in the degeneracy ordering
+/// in an arbitrary order, which may change between executions.
+///
+/// # Arguments
+/// * `graph`: &G - The graph, with both directions of every edge and sorted successors.
+pub fn par_core_decomposition<G>(graph: &G) -> CoreDecomposition<G::Node>
+where
+    G: Successors + Sync,
+    usize: To<G::Node>,
+{
+    const NOT_REMOVED: usize = usize::MAX;
+    let number_of_nodes = graph.number_of_nodes();
+
+    let degrees: Vec<AtomicUsize> = (0..number_of_nodes)
+        .into_par_iter()
+        .map(|node| AtomicUsize::new(neighbours(graph, node.to()).count()))
+        .collect();
+    let core_numbers: Vec<AtomicUsize> = (0..number_of_nodes)
+        .into_par_iter()
+        .map(|_| AtomicUsize::new(NOT_REMOVED))
+        .collect();
+
+    let mut ordering: Vec<G::Node> = Vec::with_capacity(number_of_nodes);
+    let mut remaining: Vec<usize> = (0..number_of_nodes).collect();
+    let mut k = 0;
+
+    while !remaining.is_empty() {
+        // We skip directly to the smallest degree among the remaining nodes.
+        k = k.max(
+            remaining
+                .par_iter()
+                .map(|&node| degrees[node].load(Ordering::Relaxed))
+                .min()
+                .unwrap(),
+        );
+
+        let mut frontier: Vec<usize> = remaining
+            .par_iter()
+            .copied()
+            .filter(|&node| degrees[node].load(Ordering::Relaxed) <= k)
+            .collect();
+
+        while !frontier.is_empty() {
+            frontier.par_iter().for_each(|&node| {
+                core_numbers[node].store(k, Ordering::Relaxed);
+            });
+            ordering.extend(frontier.iter().map(|&node| node.to()));
+
+            // A neighbour joins the next frontier when its degree drops to k,
+            // which happens for exactly one of the decrements.
+            frontier = frontier
+                .par_iter()
+                .flat_map_iter(|&node| neighbours(graph, node.to()))
+                .filter_map(|neighbour| {
+                    let neighbour: usize = neighbour.to();
+                    if core_numbers[neighbour].load(Ordering::Relaxed) != NOT_REMOVED {
+                        return None;
+                    }
+                    let previous_degree = degrees[neighbour].fetch_sub(1, Ordering::Relaxed);
+                    (previous_degree == k + 1).then_some(neighbour)
+                })
+                .collect();
+        }
+
+        remaining = remaining
+            .into_par_iter()
+            .filter(|&node| core_numbers[node].load(Ordering::Relaxed) == NOT_REMOVED)
+            .collect();
+        k += 1;
+    }
+
+    CoreDecomposition {
+        core_numbers: core_numbers
+            .into_iter()
+            .map(AtomicUsize::into_inner)
+            .collect(),
+        ordering,
+    }
+}
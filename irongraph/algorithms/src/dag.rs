@@ -0,0 +1,332 @@
+//! Module providing topological sorting, cycle detection and utilities for
+//! directed acyclic graphs.
+//!
+//! The topological sort is Kahn's algorithm, processing in parallel all the
+//! nodes whose predecessors have already been sorted. The resulting levels
+//! are the longest distances from the sources of the graph, which makes the
+//! longest paths available at no additional cost.
+use crate::utils::index;
+use core::fmt::{Debug, Display};
+use core::sync::atomic::{AtomicUsize, Ordering};
+use csr::prelude::*;
+use graph::Successors;
+use irontraits::To;
+use rayon::prelude::*;
+
+/// A cycle found in a graph expected to be acyclic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<N> {
+    /// The nodes of the cycle, where each node has an edge towards the next
+    /// one and the last node has an edge towards the first one.
+    nodes: Vec<N>,
+}
+
+impl<N> Cycle<N> {
+    /// Returns the nodes of the cycle.
+    #[inline(always)]
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+}
+
+impl<N> Display for Cycle<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "The graph contains a cycle of length {}.",
+            self.nodes.len()
+        )
+    }
+}
+
+impl<N: Debug> std::error::Error for Cycle<N> {}
+
+/// A topological order of a directed acyclic graph, split in levels.
+#[derive(Debug, Clone)]
+pub struct TopologicalOrder<N> {
+    /// The nodes sorted topologically.
+    order: Vec<N>,
+    /// Where each level starts in the order.
+    level_offsets: Vec<usize>,
+    /// The level of each node, i.e. the length of the longest path ending in it.
+    depths: Vec<usize>,
+    /// The predecessor of each node in one of the longest paths ending in it.
+    parents: Vec<usize>,
+}
+
+impl<N: Copy> TopologicalOrder<N> {
+    /// Returns the nodes sorted topologically.
+    #[inline(always)]
+    pub fn order(&self) -> &[N] {
+        &self.order
+    }
+
+    /// Returns the number of levels, i.e. the number of nodes in the longest path.
+    #[inline(always)]
+    pub fn number_of_levels(&self) -> usize {
+        self.level_offsets.len() - 1
+    }
+
+    /// Returns the nodes of the provided level, whose predecessors all
+    /// belong to the previous levels.
+    #[inline(always)]
+    pub fn level(&self, level: usize) -> &[N] {
+        &self.order[self.level_offsets[level]..self.level_offsets[level + 1]]
+    }
+
+    /// Returns the length in edges of the longest path ending in each node.
+    #[inline(always)]
+    pub fn depths(&self) -> &[usize] {
+        &self.depths
+    }
+
+    /// Returns one of the longest paths of the graph.
+    pub fn longest_path(&self) -> Vec<N>
+    where
+        usize: To<N>,
+    {
+        match self
+            .depths
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, depth)| **depth)
+        {
+            Some((deepest, _)) => self.longest_path_to(deepest),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns one of the longest paths ending in the provided node.
+    pub fn longest_path_to(&self, node: usize) -> Vec<N>
+    where
+        usize: To<N>,
+    {
+        let mut path = Vec::with_capacity(self.depths[node] + 1);
+        let mut node = node;
+        path.push(node.to());
+        for _ in 0..self.depths[node] {
+            node = self.parents[node];
+            path.push(node.to());
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// Sorts topologically the provided directed graph.
+///
+/// # Arguments
+/// * `graph`: &G - The graph to sort.
+///
+/// # Errors
+/// If the graph is not acyclic, returns one of its cycles.
+pub fn topological_sort<G>(graph: &G) -> Result<TopologicalOrder<G::Node>, Cycle<G::Node>>
+where
+    G: Successors + Sync,
+    usize: To<G::Node>,
+{
+    let number_of_nodes = graph.number_of_nodes();
+    let in_degrees: Vec<AtomicUsize> = (0..number_of_nodes).map(|_| AtomicUsize::new(0)).collect();
+    (0..number_of_nodes).into_par_iter().for_each(|src| {
+        let src: G::Node = src.to();
+        for dst in graph.successors(src) {
+            let dst: usize = dst.to();
+            in_degrees[dst].fetch_add(1, Ordering::Relaxed);
+        }
+    });
+    let parents: Vec<AtomicUsize> = (0..number_of_nodes).map(AtomicUsize::new).collect();
+
+    let mut order: Vec<G::Node> = Vec::with_capacity(number_of_nodes);
+    let mut level_offsets = vec![0];
+    let mut frontier: Vec<usize> = (0..number_of_nodes)
+        .into_par_iter()
+        .filter(|&node| in_degrees[node].load(Ordering::Relaxed) == 0)
+        .collect();
+
+    while !frontier.is_empty() {
+        order.extend(frontier.iter().map(|&node| node.to()));
+        level_offsets.push(order.len());
+
+        // The node decrementing the in-degree to zero is in the last level
+        // among the predecessors, and therefore the parent in a longest path.
+        frontier = frontier
+            .par_iter()
+            .flat_map_iter(|&src| {
+                let in_degrees = &in_degrees;
+                let parents = &parents;
+                graph
+                    .successors(src.to())
+                    .into_iter()
+                    .filter_map(move |dst| {
+                        let dst: usize = dst.to();
+                        if in_degrees[dst].fetch_sub(1, Ordering::Relaxed) == 1 {
+                            parents[dst].store(src, Ordering::Relaxed);
+                            Some(dst)
+                        } else {
+                            None
+                        }
+                    })
+            })
+            .collect();
+    }
+
+    if order.len() < number_of_nodes {
+        let mut colors = vec![Color::White; number_of_nodes];
+        for &node in order.iter() {
+            let node: usize = node.to();
+            colors[node] = Color::Black;
+        }
+        return Err(dfs_cycle(graph, &mut colors)
+            .expect("The nodes left out of the topological order must contain a cycle."));
+    }
+
+    let mut depths = vec![0; number_of_nodes];
+    for level in 0..level_offsets.len() - 1 {
+        for &node in &order[level_offsets[level]..level_offsets[level + 1]] {
+            let node: usize = node.to();
+            depths[node] = level;
+        }
+    }
+
+    Ok(TopologicalOrder {
+        order,
+        level_offsets,
+        depths,
+        parents: parents.into_iter().map(AtomicUsize::into_inner).collect(),
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Color {
+    /// Not visited yet.
+    White,
+    /// On the stack of the visit.
+    Grey,
+    /// Completely visited.
+    Black,
+}
+
+/// Returns a cycle through the white nodes, if any, with an iterative depth-first visit.
+fn dfs_cycle<G>(graph: &G, colors: &mut [Color]) -> Option<Cycle<G::Node>>
+where
+    G: Successors,
+    usize: To<G::Node>,
+{
+    let mut stack = Vec::new();
+    for root in 0..graph.number_of_nodes() {
+        if colors[root] != Color::White {
+            continue;
+        }
+        colors[root] = Color::Grey;
+        let root: G::Node = root.to();
+        stack.push((root, graph.successors(root).into_iter()));
+
+        while let Some((node, successors)) = stack.last_mut() {
+            let node = *node;
+            match successors.next() {
+                Some(dst) => match colors[index(dst)] {
+                    Color::White => {
+                        colors[index(dst)] = Color::Grey;
+                        stack.push((dst, graph.successors(dst).into_iter()));
+                    }
+                    Color::Grey => {
+                        let start = stack.iter().position(|(node, _)| *node == dst).unwrap();
+                        return Some(Cycle {
+                            nodes: stack[start..].iter().map(|(node, _)| *node).collect(),
+                        });
+                    }
+                    Color::Black => {}
+                },
+                None => {
+                    colors[index(node)] = Color::Black;
+                    stack.pop();
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Returns one of the cycles of the provided directed graph, if any.
+pub fn find_cycle<G>(graph: &G) -> Option<Cycle<G::Node>>
+where
+    G: Successors,
+    usize: To<G::Node>,
+{
+    dfs_cycle(graph, &mut vec![Color::White; graph.number_of_nodes()])
+}
+
+/// Returns whether the provided directed graph is acyclic.
+pub fn is_acyclic<G>(graph: &G) -> bool
+where
+    G: Successors,
+    usize: To<G::Node>,
+{
+    find_cycle(graph).is_none()
+}
+
+/// Returns the transitive reduction of the provided directed acyclic graph,
+/// i.e. the graph with the fewest edges having the same reachability.
+///
+/// # Arguments
+/// * `graph`: &G - The graph, with sorted successors.
+///
+/// # Errors
+/// If the graph is not acyclic, returns one of its cycles.
+pub fn transitive_reduction<G>(graph: &G) -> Result<VecCSR<G::Node>, Cycle<G::Node>>
+where
+    G: Successors + Sync,
+    usize: To<G::Node>,
+{
+    if let Some(cycle) = find_cycle(graph) {
+        return Err(cycle);
+    }
+    let number_of_nodes = graph.number_of_nodes();
+
+    // An edge is redundant when its destination is reachable from another
+    // successor of its source.
+    let successors: Vec<Vec<G::Node>> = (0..number_of_nodes)
+        .into_par_iter()
+        .map_init(
+            || (vec![usize::MAX; number_of_nodes], Vec::new()),
+            |(visited, stack), src| {
+                let src_node: G::Node = src.to();
+                for dst in graph.successors(src_node) {
+                    for next in graph.successors(dst) {
+                        if visited[index(next)] != src {
+                            visited[index(next)] = src;
+                            stack.push(next);
+                        }
+                    }
+                }
+                while let Some(node) = stack.pop() {
+                    for next in graph.successors(node) {
+                        if visited[index(next)] != src {
+                            visited[index(next)] = src;
+                            stack.push(next);
+                        }
+                    }
+                }
+                let mut kept: Vec<G::Node> = graph
+                    .successors(src_node)
+                    .into_iter()
+                    .filter(|&dst| visited[index(dst)] != src)
+                    .collect();
+                kept.dedup();
+                kept
+            },
+        )
+        .collect();
+
+    let number_of_edges = successors.iter().map(Vec::len).sum();
+    Ok(CSRBuilder::default()
+        .number_of_nodes(number_of_nodes)
+        .number_of_edges(number_of_edges)
+        .sorted()
+        .build(
+            successors
+                .iter()
+                .enumerate()
+                .flat_map(|(src, dsts)| dsts.iter().map(move |&dst| (src.to(), dst))),
+        ))
+}
@@ -0,0 +1,69 @@
+//! Module providing the batch interface shared by the edge feature extractors.
+//!
+//! An extractor describes the features it writes for each edge, and the
+//! features of a batch of edges are written in parallel in a dense row-major
+//! matrix of shape `edges × feature_len`.
+use rayon::prelude::*;
+
+/// Trait for the extractors of a fixed number of features for each edge.
+pub trait EdgeFeatures: Sync {
+    /// The type of the nodes of the edges.
+    type Node: Copy + Send;
+
+    /// Returns the number of features written for each edge.
+    fn feature_len(&self) -> usize;
+
+    /// Returns the names of the features, in the order in which they are written.
+    fn feature_names(&self) -> Vec<String>;
+
+    /// Writes the features of the edge into the target row.
+    ///
+    /// # Arguments
+    /// * `edge`: (Self::Node, Self::Node) - The source and destination of the edge.
+    /// * `target`: &mut [f32] - The zeroed row of length `feature_len`.
+    fn edge_features(&self, edge: (Self::Node, Self::Node), target: &mut [f32]);
+
+    /// Writes the features of the edges into the rows of the target matrix, in parallel.
+    ///
+    /// # Arguments
+    /// * `edges`: I - The edges, as an indexed parallel iterator or a collection providing one.
+    /// * `target`: &mut [f32] - The row-major matrix of shape `edges × feature_len`.
+    ///
+    /// # Panics
+    /// If the target matrix does not have the expected length.
+    fn par_edge_features<I>(&self, edges: I, target: &mut [f32])
+    where
+        I: IntoParallelIterator<Item = (Self::Node, Self::Node)>,
+        I::Iter: IndexedParallelIterator,
+    {
+        let edges = edges.into_par_iter();
+        let feature_len = self.feature_len();
+        assert_eq!(
+            target.len(),
+            edges.len() * feature_len,
+            "The target matrix must have shape edges × feature length."
+        );
+        if feature_len == 0 {
+            return;
+        }
+        edges
+            .zip(target.par_chunks_exact_mut(feature_len))
+            .for_each(|(edge, row)| {
+                row.fill(0.0);
+                self.edge_features(edge, row);
+            });
+    }
+
+    /// Returns the row-major matrix of shape `edges × feature_len` with
+    /// the features of the edges, computed in parallel.
+    fn par_edge_features_matrix<I>(&self, edges: I) -> Vec<f32>
+    where
+        I: IntoParallelIterator<Item = (Self::Node, Self::Node)>,
+        I::Iter: IndexedParallelIterator,
+    {
+        let edges = edges.into_par_iter();
+        let mut target = vec![0.0; edges.len() * self.feature_len()];
+        self.par_edge_features(edges, &mut target);
+        target
+    }
+}
@@ -0,0 +1,199 @@
+//! Module providing the classic link prediction heuristics, exact baseline
+//! scores for the edges computed from the neighbourhoods of their endpoints.
+//!
+//! The scores are written through the [`EdgeFeatures`] batch interface, so
+//! that they can be computed for batches of edges in parallel and stacked
+//! with the features of the sketches. Self-loops and duplicated edges are
+//! ignored, so the degree of a node is its number of distinct neighbours.
+use crate::features::EdgeFeatures;
+use crate::utils::{for_each_common, neighbours};
+use graph::Successors;
+
+/// The names of the heuristic scores, in the order in which they are written.
+const SCORE_NAMES: [&str; 6] = [
+    "common_neighbours",
+    "jaccard",
+    "adamic_adar",
+    "resource_allocation",
+    "preferential_attachment",
+    "katz",
+];
+
+/// Parameters of the link prediction heuristics.
+#[derive(Debug, Clone)]
+pub struct LinkPrediction {
+    /// The maximal length of the walks counted by the Katz index.
+    katz_hops: usize,
+    /// The damping of the walks counted by the Katz index.
+    katz_beta: f64,
+}
+
+impl Default for LinkPrediction {
+    fn default() -> Self {
+        LinkPrediction {
+            katz_hops: 3,
+            katz_beta: 0.05,
+        }
+    }
+}
+
+impl LinkPrediction {
+    /// Sets the maximal length of the walks counted by the Katz index.
+    ///
+    /// # Arguments
+    /// * `katz_hops`: usize - The maximal length of the walks, by default 3.
+    ///
+    /// # Panics
+    /// If the maximal length is zero.
+    pub fn katz_hops(mut self, katz_hops: usize) -> Self {
+        assert!(
+            katz_hops > 0,
+            "The Katz index needs walks of at least one hop."
+        );
+        self.katz_hops = katz_hops;
+        self
+    }
+
+    /// Sets the damping of the walks counted by the Katz index, so that a
+    /// walk of length `l` contributes `beta^l` to the score.
+    ///
+    /// # Arguments
+    /// * `katz_beta`: f64 - The damping of the walks, by default 0.05.
+    ///
+    /// # Panics
+    /// If the damping is not strictly between 0 and 1.
+    pub fn katz_beta(mut self, katz_beta: f64) -> Self {
+        assert!(
+            katz_beta > 0.0 && katz_beta < 1.0,
+            "The Katz damping must be strictly between 0 and 1."
+        );
+        self.katz_beta = katz_beta;
+        self
+    }
+
+    /// Returns the extractor of the heuristic scores of the edges of the graph.
+    ///
+    /// # Arguments
+    /// * `graph`: &G - The graph, with sorted successors.
+    pub fn scores<'a, G: Successors>(&self, graph: &'a G) -> LinkPredictionScores<'a, G> {
+        LinkPredictionScores {
+            parameters: self.clone(),
+            graph,
+        }
+    }
+}
+
+/// The link prediction heuristic scores of the edges of a graph.
+///
+/// For each edge `(src, dst)` the scores are, in order:
+/// * the number of common neighbours of the endpoints;
+/// * the Jaccard coefficient of their neighbourhoods;
+/// * the Adamic–Adar index, summing `1 / ln(degree)` over the common neighbours;
+/// * the resource allocation index, summing `1 / degree` over the common neighbours;
+/// * the preferential attachment, the product of the degrees of the endpoints;
+/// * the Katz index truncated to walks of at most `katz_hops` hops.
+///
+/// On directed graphs the neighbours are the successors, so the common
+/// neighbours are the nodes reachable from both endpoints and the Katz
+/// index counts the walks from the source to the destination.
+#[derive(Debug, Clone)]
+pub struct LinkPredictionScores<'a, G> {
+    /// The parameters of the heuristics.
+    parameters: LinkPrediction,
+    /// The graph whose edges are scored.
+    graph: &'a G,
+}
+
+impl<G: Successors> LinkPredictionScores<'_, G> {
+    /// Returns the Katz index of the edge truncated to the walks of at most
+    /// `katz_hops` hops.
+    fn katz(&self, src: G::Node, dst: G::Node) -> f64 {
+        let beta = self.parameters.katz_beta;
+        // The number of walks from the source ending at each node, sorted by node.
+        let mut walks = vec![(src, 1.0)];
+        let mut damping = 1.0;
+        let mut score = 0.0;
+        for hop in 0..self.parameters.katz_hops {
+            damping *= beta;
+            if hop + 1 == self.parameters.katz_hops {
+                // The walks of the last hop only matter if they end in the destination.
+                score += damping
+                    * walks
+                        .iter()
+                        .filter(|&&(node, _)| node != dst && self.graph.has_successor(node, dst))
+                        .map(|&(_, count)| count)
+                        .sum::<f64>();
+                break;
+            }
+            let mut next: Vec<(G::Node, f64)> = walks
+                .iter()
+                .flat_map(|&(node, count)| {
+                    neighbours(self.graph, node).map(move |successor| (successor, count))
+                })
+                .collect();
+            next.sort_unstable_by_key(|&(node, _)| node);
+            next.dedup_by(|(node, count), (previous, total)| {
+                let is_duplicate = node == previous;
+                if is_duplicate {
+                    *total += *count;
+                }
+                is_duplicate
+            });
+            if let Ok(position) = next.binary_search_by_key(&dst, |&(node, _)| node) {
+                score += damping * next[position].1;
+            }
+            walks = next;
+        }
+        score
+    }
+}
+
+impl<G> EdgeFeatures for LinkPredictionScores<'_, G>
+where
+    G: Successors + Sync,
+{
+    type Node = G::Node;
+
+    #[inline(always)]
+    fn feature_len(&self) -> usize {
+        SCORE_NAMES.len()
+    }
+
+    #[inline(always)]
+    fn feature_names(&self) -> Vec<String> {
+        SCORE_NAMES.iter().map(|name| name.to_string()).collect()
+    }
+
+    fn edge_features(&self, (src, dst): (G::Node, G::Node), target: &mut [f32]) {
+        let left: Vec<G::Node> = neighbours(self.graph, src).collect();
+        let right: Vec<G::Node> = neighbours(self.graph, dst).collect();
+
+        let mut common_neighbours = 0;
+        let mut adamic_adar = 0.0;
+        let mut resource_allocation = 0.0;
+        for_each_common(&left, &right, |node| {
+            let degree = neighbours(self.graph, node).count();
+            common_neighbours += 1;
+            if degree > 0 {
+                resource_allocation += 1.0 / degree as f64;
+            }
+            // The neighbours of degree one would have an infinite weight,
+            // which only happens on directed graphs or for self-loops.
+            if degree > 1 {
+                adamic_adar += 1.0 / (degree as f64).ln();
+            }
+        });
+        let union = left.len() + right.len() - common_neighbours;
+
+        target[0] = common_neighbours as f32;
+        target[1] = if union == 0 {
+            0.0
+        } else {
+            common_neighbours as f32 / union as f32
+        };
+        target[2] = adamic_adar as f32;
+        target[3] = resource_allocation as f32;
+        target[4] = (left.len() * right.len()) as f32;
+        target[5] = self.katz(src, dst) as f32;
+    }
+}
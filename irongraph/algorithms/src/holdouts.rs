@@ -0,0 +1,313 @@
+//! Module providing train and test splits of the edges of a graph, to train
+//! and evaluate link prediction models.
+//!
+//! Unless the holdout is directed, the edges are split as undirected edges:
+//! both directions of an edge always end up in the same split, and the graph
+//! must be symmetric.
+//!
+//! The edges are shuffled by Xoshiro256++ seeded with the seed of the
+//! holdout, so that the splits of a seed are the same on every platform.
+use crate::utils::{index, DisjointSets};
+use core::fmt::{Debug, Display};
+use csr::prelude::*;
+use graph::Successors;
+use irontraits::{PositiveInteger, To};
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_xoshiro::Xoshiro256PlusPlus;
+use rayon::prelude::*;
+
+/// An edge whose reverse is missing, proving that a graph to split as
+/// undirected is not symmetric.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AsymmetricEdge<N> {
+    /// The source of the edge.
+    src: N,
+    /// The destination of the edge, which has no edge towards the source.
+    dst: N,
+}
+
+impl<N: Copy> AsymmetricEdge<N> {
+    /// Returns the source of the edge.
+    #[inline(always)]
+    pub fn src(&self) -> N {
+        self.src
+    }
+
+    /// Returns the destination of the edge.
+    #[inline(always)]
+    pub fn dst(&self) -> N {
+        self.dst
+    }
+}
+
+impl<N: Debug> Display for AsymmetricEdge<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "The graph is not symmetric, as it has the edge from {:?} to {:?} but not its reverse. \
+            Use a directed holdout to split directed graphs.",
+            self.src, self.dst
+        )
+    }
+}
+
+impl<N: Debug> std::error::Error for AsymmetricEdge<N> {}
+
+/// Returns the first edge of the graph whose reverse is missing, if any.
+fn asymmetric_edge<G>(graph: &G) -> Option<AsymmetricEdge<G::Node>>
+where
+    G: Successors + Sync,
+    usize: To<G::Node>,
+{
+    (0..graph.number_of_nodes())
+        .into_par_iter()
+        .find_map_first(|src| {
+            let src: G::Node = src.to();
+            graph
+                .successors(src)
+                .into_iter()
+                .find(|&dst| !graph.has_successor(dst, src))
+                .map(|dst| AsymmetricEdge { src, dst })
+        })
+}
+
+/// The train and test graphs of a holdout, with the nodes of the original graph.
+#[derive(Debug, Clone)]
+pub struct EdgeSplit<N> {
+    train: VecCSR<N>,
+    test: VecCSR<N>,
+}
+
+impl<N> EdgeSplit<N> {
+    /// Returns the graph with the edges to train on.
+    #[inline(always)]
+    pub fn train(&self) -> &VecCSR<N> {
+        &self.train
+    }
+
+    /// Returns the graph with the held out edges.
+    #[inline(always)]
+    pub fn test(&self) -> &VecCSR<N> {
+        &self.test
+    }
+
+    /// Returns the train and test graphs.
+    #[inline(always)]
+    pub fn into_parts(self) -> (VecCSR<N>, VecCSR<N>) {
+        (self.train, self.test)
+    }
+}
+
+/// Parameters of the edge holdouts.
+#[derive(Debug, Clone)]
+pub struct Holdout {
+    /// The fraction of the edges to hold out in the test graph.
+    test_fraction: f64,
+    /// Whether to split the edges as directed edges.
+    directed: bool,
+    /// The seed of the holdout.
+    seed: u64,
+}
+
+impl Default for Holdout {
+    fn default() -> Self {
+        Holdout {
+            test_fraction: 0.2,
+            directed: false,
+            seed: 0xa409_3822_299f_31d0,
+        }
+    }
+}
+
+impl Holdout {
+    /// Sets the fraction of the edges to hold out in the test graph.
+    ///
+    /// # Panics
+    /// If the fraction is not in `[0, 1]`.
+    pub fn test_fraction(mut self, test_fraction: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&test_fraction),
+            "The test fraction must be in [0, 1]."
+        );
+        self.test_fraction = test_fraction;
+        self
+    }
+
+    /// Sets whether to split the edges as directed edges, instead of
+    /// keeping both directions of each edge in the same split.
+    ///
+    /// The holdouts are undirected by default, and then refuse the graphs
+    /// which are not symmetric.
+    pub fn directed(mut self, directed: bool) -> Self {
+        self.directed = directed;
+        self
+    }
+
+    /// Sets the seed of the holdout.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Returns the edges to split, shuffled.
+    ///
+    /// # Errors
+    /// If the holdout is undirected and the graph is not symmetric, returns
+    /// the first edge whose reverse is missing.
+    fn shuffled_edges<G>(&self, graph: &G) -> Result<Vec<(usize, usize)>, AsymmetricEdge<G::Node>>
+    where
+        G: Successors + Sync,
+        usize: To<G::Node>,
+    {
+        if !self.directed {
+            if let Some(asymmetric_edge) = asymmetric_edge(graph) {
+                return Err(asymmetric_edge);
+            }
+        }
+
+        let mut edges: Vec<(usize, usize)> = (0..graph.number_of_nodes())
+            .into_par_iter()
+            .flat_map_iter(|src| {
+                graph
+                    .successors(src.to())
+                    .into_iter()
+                    .map(index)
+                    .filter(move |&dst| self.directed || src <= dst)
+                    .map(move |dst| (src, dst))
+            })
+            .collect();
+        edges.shuffle(&mut Xoshiro256PlusPlus::seed_from_u64(self.seed));
+        Ok(edges)
+    }
+
+    /// Returns the number of edges to hold out among the provided ones.
+    #[inline(always)]
+    fn number_of_test_edges(&self, number_of_edges: usize) -> usize {
+        (self.test_fraction * number_of_edges as f64).round() as usize
+    }
+
+    /// Returns the graph with the selected edges, adding the reverse ones
+    /// unless the holdout is directed.
+    fn build<N>(
+        &self,
+        number_of_nodes: usize,
+        edges: impl Iterator<Item = (usize, usize)>,
+    ) -> VecCSR<N>
+    where
+        N: PositiveInteger,
+        usize: To<N>,
+    {
+        let mut edges: Vec<(usize, usize)> = if self.directed {
+            edges.collect()
+        } else {
+            edges
+                .flat_map(|(src, dst)| {
+                    core::iter::once((src, dst)).chain((src != dst).then_some((dst, src)))
+                })
+                .collect()
+        };
+        edges.par_sort_unstable();
+
+        CSRBuilder::default()
+            .number_of_nodes(number_of_nodes)
+            .number_of_edges(edges.len())
+            .sorted()
+            .build(edges.into_iter().map(|(src, dst)| (src.to(), dst.to())))
+    }
+
+    /// Returns the split where each edge is held out with the same probability.
+    ///
+    /// # Errors
+    /// If the holdout is undirected and the graph is not symmetric, returns
+    /// an edge whose reverse is missing.
+    pub fn uniform<G>(&self, graph: &G) -> Result<EdgeSplit<G::Node>, AsymmetricEdge<G::Node>>
+    where
+        G: Successors + Sync,
+        usize: To<G::Node>,
+    {
+        let edges = self.shuffled_edges(graph)?;
+        let (test, train) = edges.split_at(self.number_of_test_edges(edges.len()));
+        Ok(EdgeSplit {
+            train: self.build(graph.number_of_nodes(), train.iter().copied()),
+            test: self.build(graph.number_of_nodes(), test.iter().copied()),
+        })
+    }
+
+    /// Returns the split where the edges of a random spanning forest are
+    /// kept in the train graph, so that it has the same connected components
+    /// as the original graph.
+    ///
+    /// When the edges outside of the forest are fewer than the edges to
+    /// hold out, they are all held out.
+    ///
+    /// # Errors
+    /// If the holdout is undirected and the graph is not symmetric, returns
+    /// an edge whose reverse is missing.
+    pub fn connected<G>(&self, graph: &G) -> Result<EdgeSplit<G::Node>, AsymmetricEdge<G::Node>>
+    where
+        G: Successors + Sync,
+        usize: To<G::Node>,
+    {
+        let edges = self.shuffled_edges(graph)?;
+        let number_of_test_edges = self.number_of_test_edges(edges.len());
+
+        // Kruskal's algorithm on the shuffled edges builds a random spanning forest.
+        let mut sets = DisjointSets::new(graph.number_of_nodes());
+        let (forest, others): (Vec<_>, Vec<_>) = edges
+            .into_iter()
+            .partition(|&(src, dst)| sets.union(src, dst));
+        let (test, train) = others.split_at(number_of_test_edges.min(others.len()));
+
+        Ok(EdgeSplit {
+            train: self.build(
+                graph.number_of_nodes(),
+                forest.into_iter().chain(train.iter().copied()),
+            ),
+            test: self.build(graph.number_of_nodes(), test.iter().copied()),
+        })
+    }
+
+    /// Returns the splits of a k-fold cross-validation, where the shuffled
+    /// edges are split in folds of nearly the same size, and each fold is the
+    /// test graph of one of the splits.
+    ///
+    /// The test fraction is ignored, as it is one over the number of folds.
+    ///
+    /// # Panics
+    /// If the number of folds is smaller than two.
+    ///
+    /// # Errors
+    /// If the holdout is undirected and the graph is not symmetric, returns
+    /// an edge whose reverse is missing.
+    pub fn k_fold<G>(
+        &self,
+        graph: &G,
+        number_of_folds: usize,
+    ) -> Result<Vec<EdgeSplit<G::Node>>, AsymmetricEdge<G::Node>>
+    where
+        G: Successors + Sync,
+        usize: To<G::Node>,
+    {
+        assert!(
+            number_of_folds >= 2,
+            "The number of folds must be at least 2."
+        );
+        let edges = self.shuffled_edges(graph)?;
+        let fold_boundary = |fold: usize| fold * edges.len() / number_of_folds;
+
+        Ok((0..number_of_folds)
+            .into_par_iter()
+            .map(|fold| {
+                let (start, end) = (fold_boundary(fold), fold_boundary(fold + 1));
+                EdgeSplit {
+                    train: self.build(
+                        graph.number_of_nodes(),
+                        edges[..start].iter().chain(&edges[end..]).copied(),
+                    ),
+                    test: self.build(graph.number_of_nodes(), edges[start..end].iter().copied()),
+                }
+            })
+            .collect())
+    }
+}
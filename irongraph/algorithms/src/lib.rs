@@ -0,0 +1,36 @@
+//! Parallel graph algorithms over the traits provided by the graph crate.
+//!
+//! Unless stated otherwise, the algorithms expect the successors of each node
+//! to be sorted, as it is the case for the CSR built with the `CSRBuilder`,
+//! and undirected graphs to be represented by storing both directions of
+//! every edge.
+#![deny(unconditional_recursion)]
+
+pub mod bipartite;
+pub mod centrality;
+pub mod communities;
+pub mod cores;
+pub mod dag;
+pub mod features;
+pub mod heuristics;
+pub mod holdouts;
+pub mod negatives;
+pub mod spanning;
+pub mod triangles;
+mod utils;
+pub mod walks;
+
+pub mod prelude {
+    pub use super::bipartite::*;
+    pub use super::centrality::*;
+    pub use super::communities::*;
+    pub use super::cores::*;
+    pub use super::dag::*;
+    pub use super::features::*;
+    pub use super::heuristics::*;
+    pub use super::holdouts::*;
+    pub use super::negatives::*;
+    pub use super::spanning::*;
+    pub use super::triangles::*;
+    pub use super::walks::*;
+}
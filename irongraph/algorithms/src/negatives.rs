@@ -0,0 +1,223 @@
+//! Module providing parallel sampling of negative edges, i.e. pairs of
+//! nodes that are not connected, to train link prediction models.
+//!
+//! Every negative edge is sampled with its own random number generator,
+//! seeded from the sampler seed and the index of the edge, so that the
+//! negatives do not depend on how they are scheduled across the threads. The
+//! generator is Xoshiro256++, so that the negatives of a seed are the same
+//! on every platform.
+use crate::utils::{degree, random_successor, splitmix64};
+use graph::Successors;
+use irontraits::To;
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+use rayon::prelude::*;
+
+/// How the endpoints of the negative edges are chosen.
+#[derive(Debug, Clone, Copy)]
+enum Strategy {
+    /// Both endpoints are chosen uniformly among the nodes.
+    Uniform,
+    /// Both endpoints are chosen with probability proportional to their degree.
+    DegreeBiased,
+    /// The destination is the end of a random walk from the source of at
+    /// most the provided number of steps, so that it is within that many hops.
+    Hard { hops: usize },
+}
+
+/// Parameters of the negative edge sampling.
+///
+/// A negative edge is never a self-loop, an edge of the graph or an edge of
+/// the excluded graph, if provided.
+#[derive(Debug, Clone)]
+pub struct NegativeSampler {
+    /// How the endpoints of the negative edges are chosen.
+    strategy: Strategy,
+    /// How many candidates to reject before giving up on a negative edge.
+    maximum_attempts: usize,
+    /// The seed of the sampling.
+    seed: u64,
+}
+
+impl Default for NegativeSampler {
+    fn default() -> Self {
+        NegativeSampler {
+            strategy: Strategy::Uniform,
+            maximum_attempts: 1_000,
+            seed: 0x1f83_d9ab_fb41_bd6b,
+        }
+    }
+}
+
+impl NegativeSampler {
+    /// Chooses both endpoints uniformly among the nodes.
+    pub fn uniform(mut self) -> Self {
+        self.strategy = Strategy::Uniform;
+        self
+    }
+
+    /// Chooses both endpoints with probability proportional to their degree,
+    /// so that the negatives follow the same degree distribution as the edges.
+    pub fn degree_biased(mut self) -> Self {
+        self.strategy = Strategy::DegreeBiased;
+        self
+    }
+
+    /// Chooses the destinations within the provided number of hops from the
+    /// sources, which makes the negatives harder to tell from the edges.
+    ///
+    /// # Panics
+    /// If the number of hops is smaller than two.
+    pub fn hard(mut self, hops: usize) -> Self {
+        assert!(
+            hops >= 2,
+            "The hard negatives must be at least 2 hops away."
+        );
+        self.strategy = Strategy::Hard { hops };
+        self
+    }
+
+    /// Sets how many candidates to reject before giving up on a negative
+    /// edge, which bounds the sampling time on dense graphs.
+    ///
+    /// # Panics
+    /// If the number of attempts is zero.
+    pub fn maximum_attempts(mut self, maximum_attempts: usize) -> Self {
+        assert!(
+            maximum_attempts > 0,
+            "The maximum number of attempts must be greater than 0."
+        );
+        self.maximum_attempts = maximum_attempts;
+        self
+    }
+
+    /// Sets the seed of the sampling.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Returns the provided number of negative edges of the graph.
+    ///
+    /// The negatives that could not be sampled within the maximum number of
+    /// attempts are skipped, so fewer negatives may be returned.
+    pub fn sample<G>(&self, graph: &G, number_of_negatives: usize) -> Vec<(G::Node, G::Node)>
+    where
+        G: Successors + Sync,
+        usize: To<G::Node>,
+    {
+        self.sample_dispatched(graph, None::<&G>, number_of_negatives)
+    }
+
+    /// Returns the provided number of negative edges of the graph that are
+    /// not edges of the excluded graph either, e.g. the test graph of a holdout.
+    ///
+    /// The negatives that could not be sampled within the maximum number of
+    /// attempts are skipped, so fewer negatives may be returned.
+    pub fn sample_excluding<G, E>(
+        &self,
+        graph: &G,
+        excluded: &E,
+        number_of_negatives: usize,
+    ) -> Vec<(G::Node, G::Node)>
+    where
+        G: Successors + Sync,
+        E: Successors<Node = G::Node> + Sync,
+        usize: To<G::Node>,
+    {
+        self.sample_dispatched(graph, Some(excluded), number_of_negatives)
+    }
+
+    fn sample_dispatched<G, E>(
+        &self,
+        graph: &G,
+        excluded: Option<&E>,
+        number_of_negatives: usize,
+    ) -> Vec<(G::Node, G::Node)>
+    where
+        G: Successors + Sync,
+        E: Successors<Node = G::Node> + Sync,
+        usize: To<G::Node>,
+    {
+        let number_of_nodes = graph.number_of_nodes();
+        if number_of_nodes < 2 {
+            return Vec::new();
+        }
+
+        // The cumulative degrees, to sample the nodes proportionally to the degree.
+        let cumulative_degrees: Vec<usize> = match self.strategy {
+            Strategy::DegreeBiased => {
+                let degrees: Vec<usize> = (0..number_of_nodes)
+                    .into_par_iter()
+                    .map(|node| degree(graph, node.to()))
+                    .collect();
+                core::iter::once(0)
+                    .chain(degrees.into_iter().scan(0, |total, degree| {
+                        *total += degree;
+                        Some(*total)
+                    }))
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+
+        (0..number_of_negatives)
+            .into_par_iter()
+            .filter_map(|negative| {
+                let mut rng = Xoshiro256PlusPlus::seed_from_u64(splitmix64(
+                    self.seed.wrapping_add(negative as u64),
+                ));
+                (0..self.maximum_attempts).find_map(|_| {
+                    let (src, dst) = self.candidate(graph, &cumulative_degrees, &mut rng)?;
+                    let is_negative = src != dst
+                        && !graph.has_successor(src, dst)
+                        && excluded.is_none_or(|excluded| !excluded.has_successor(src, dst));
+                    is_negative.then_some((src, dst))
+                })
+            })
+            .collect()
+    }
+
+    /// Returns a candidate negative edge, if the strategy found one.
+    #[inline(always)]
+    fn candidate<G, R>(
+        &self,
+        graph: &G,
+        cumulative_degrees: &[usize],
+        rng: &mut R,
+    ) -> Option<(G::Node, G::Node)>
+    where
+        G: Successors,
+        R: Rng,
+        usize: To<G::Node>,
+    {
+        let number_of_nodes = graph.number_of_nodes();
+        match self.strategy {
+            Strategy::Uniform => Some((
+                rng.gen_range(0..number_of_nodes).to(),
+                rng.gen_range(0..number_of_nodes).to(),
+            )),
+            Strategy::DegreeBiased => {
+                let total_degree = *cumulative_degrees.last().unwrap();
+                if total_degree == 0 {
+                    return None;
+                }
+                let mut by_degree = || {
+                    let position = rng.gen_range(0..total_degree);
+                    let node = cumulative_degrees.partition_point(|&total| total <= position) - 1;
+                    node.to()
+                };
+                Some((by_degree(), by_degree()))
+            }
+            Strategy::Hard { hops } => {
+                let src: G::Node = rng.gen_range(0..number_of_nodes).to();
+                let steps = rng.gen_range(2..=hops);
+                let mut dst = src;
+                for _ in 0..steps {
+                    dst = random_successor(graph, dst, rng)?;
+                }
+                Some((src, dst))
+            }
+        }
+    }
+}
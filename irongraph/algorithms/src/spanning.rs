@@ -0,0 +1,202 @@
+//! Module providing minimum spanning forests of weighted undirected graphs.
+//!
+//! Edges are compared by weight and then by their endpoints, so that all the
+//! edges are distinct and the minimum spanning forest is unique: Kruskal and
+//! Borůvka return exactly the same edges.
+use crate::utils::DisjointSets;
+use core::cmp::Ordering;
+use csr::prelude::*;
+use graph::WeightedSuccessors;
+use irontraits::{PositiveInteger, To};
+use rayon::prelude::*;
+
+/// A minimum spanning forest, i.e. a minimum spanning tree for each
+/// connected component of the graph.
+#[derive(Debug, Clone)]
+pub struct SpanningForest<N, W> {
+    /// The edges of the forest, stored in both directions.
+    forest: VecWeightedCSR<N, W>,
+    /// The sum of the weights of the edges of the forest.
+    total_weight: f64,
+}
+
+impl<N, W> SpanningForest<N, W> {
+    /// Returns the forest, with every edge stored in both directions.
+    #[inline(always)]
+    pub fn forest(&self) -> &VecWeightedCSR<N, W> {
+        &self.forest
+    }
+
+    /// Returns the forest, with every edge stored in both directions.
+    #[inline(always)]
+    pub fn into_forest(self) -> VecWeightedCSR<N, W> {
+        self.forest
+    }
+
+    /// Returns the sum of the weights of the edges of the forest, each
+    /// undirected edge counted once.
+    #[inline(always)]
+    pub fn total_weight(&self) -> f64 {
+        self.total_weight
+    }
+}
+
+/// An undirected edge, with the smaller endpoint first.
+#[derive(Debug, Clone, Copy)]
+struct Edge<W> {
+    src: usize,
+    dst: usize,
+    weight: W,
+    /// The weight converted once for the comparisons.
+    key: f64,
+}
+
+impl<W> Edge<W> {
+    #[inline(always)]
+    fn new(src: usize, dst: usize, weight: W) -> Self
+    where
+        W: To<f64> + Copy,
+    {
+        Edge {
+            src: src.min(dst),
+            dst: src.max(dst),
+            weight,
+            key: weight.to(),
+        }
+    }
+
+    /// Compares the edges by weight, breaking the ties by endpoints.
+    #[inline(always)]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key
+            .total_cmp(&other.key)
+            .then((self.src, self.dst).cmp(&(other.src, other.dst)))
+    }
+}
+
+/// Returns the minimum spanning forest of the provided graph with Kruskal's
+/// algorithm, sorting the edges in parallel.
+///
+/// # Arguments
+/// * `graph`: &G - The undirected graph, with every edge stored in both directions.
+pub fn kruskal<G>(graph: &G) -> SpanningForest<G::Node, G::Weight>
+where
+    G: WeightedSuccessors + Sync,
+    G::Weight: To<f64> + Send + Sync,
+    usize: To<G::Node>,
+{
+    let mut edges: Vec<Edge<G::Weight>> = (0..graph.number_of_nodes())
+        .into_par_iter()
+        .flat_map_iter(|src| {
+            graph
+                .weighted_successors(src.to())
+                .into_iter()
+                .filter_map(move |(dst, weight)| {
+                    let dst: usize = dst.to();
+                    (src < dst).then(|| Edge::new(src, dst, weight))
+                })
+        })
+        .collect();
+    edges.par_sort_unstable_by(Edge::cmp);
+
+    let mut sets = DisjointSets::new(graph.number_of_nodes());
+    let chosen = edges
+        .into_iter()
+        .filter(|edge| sets.union(edge.src, edge.dst))
+        .collect();
+    build_forest(graph.number_of_nodes(), chosen)
+}
+
+/// Returns the minimum spanning forest of the provided graph with Borůvka's
+/// algorithm, looking for the lightest edge leaving each node in parallel.
+///
+/// # Arguments
+/// * `graph`: &G - The undirected graph, with every edge stored in both directions.
+pub fn boruvka<G>(graph: &G) -> SpanningForest<G::Node, G::Weight>
+where
+    G: WeightedSuccessors + Sync,
+    G::Weight: To<f64> + Send + Sync,
+    usize: To<G::Node>,
+{
+    let number_of_nodes = graph.number_of_nodes();
+    let mut sets = DisjointSets::new(number_of_nodes);
+    let mut components: Vec<usize> = (0..number_of_nodes).collect();
+    let mut chosen = Vec::new();
+
+    loop {
+        let lightest: Vec<Option<Edge<G::Weight>>> = (0..number_of_nodes)
+            .into_par_iter()
+            .map(|src| {
+                graph
+                    .weighted_successors(src.to())
+                    .into_iter()
+                    .filter_map(|(dst, weight)| {
+                        let dst: usize = dst.to();
+                        (components[src] != components[dst]).then(|| Edge::new(src, dst, weight))
+                    })
+                    .min_by(Edge::cmp)
+            })
+            .collect();
+
+        // The lightest edge leaving each component.
+        let mut component_lightest: Vec<Option<Edge<G::Weight>>> = vec![None; number_of_nodes];
+        for (src, edge) in lightest.into_iter().enumerate() {
+            if let Some(edge) = edge {
+                let best = &mut component_lightest[components[src]];
+                if best.is_none_or(|best| edge.cmp(&best) == Ordering::Less) {
+                    *best = Some(edge);
+                }
+            }
+        }
+
+        // Two components may choose the same edge, which is added only once.
+        let number_of_chosen = chosen.len();
+        for edge in component_lightest.into_iter().flatten() {
+            if sets.union(edge.src, edge.dst) {
+                chosen.push(edge);
+            }
+        }
+        if chosen.len() == number_of_chosen {
+            break;
+        }
+        for (node, component) in components.iter_mut().enumerate() {
+            *component = sets.find(node);
+        }
+    }
+
+    build_forest(number_of_nodes, chosen)
+}
+
+/// Returns the forest with the provided edges stored in both directions.
+fn build_forest<N, W>(number_of_nodes: usize, chosen: Vec<Edge<W>>) -> SpanningForest<N, W>
+where
+    N: PositiveInteger,
+    W: Copy + Send,
+    usize: To<N>,
+{
+    let total_weight = chosen.iter().map(|edge| edge.key).sum();
+    let mut edges: Vec<(usize, usize, W)> = chosen
+        .into_iter()
+        .flat_map(|edge| {
+            [
+                (edge.src, edge.dst, edge.weight),
+                (edge.dst, edge.src, edge.weight),
+            ]
+        })
+        .collect();
+    edges.par_sort_unstable_by_key(|&(src, dst, _)| (src, dst));
+
+    let forest = CSRBuilder::default()
+        .number_of_nodes(number_of_nodes)
+        .number_of_edges(edges.len())
+        .sorted()
+        .build_weighted(
+            edges
+                .into_iter()
+                .map(|(src, dst, weight)| (src.to(), dst.to(), weight)),
+        );
+    SpanningForest {
+        forest,
+        total_weight,
+    }
+}
@@ -0,0 +1,154 @@
+//! Module providing exact triangle counting and local clustering coefficients.
+//!
+//! The triangles are listed by orienting every edge from the endpoint with the
+//! lower degree to the one with the higher degree, breaking ties by node id,
+//! and by merging the oriented successor lists of the endpoints of each edge.
+//! This way every triangle is found exactly once, and the length of the
+//! oriented lists is bounded by the square root of the number of edges.
+use crate::utils::{for_each_common, neighbours};
+use core::sync::atomic::{AtomicUsize, Ordering};
+use graph::Successors;
+use irontraits::To;
+use rayon::prelude::*;
+
+/// The triangles of an undirected graph.
+#[derive(Debug, Clone)]
+pub struct Triangles {
+    /// The number of triangles each node is part of.
+    node_triangles: Vec<usize>,
+    /// The degree of each node, self-loops and duplicated edges excluded.
+    degrees: Vec<usize>,
+}
+
+impl Triangles {
+    /// Returns the number of triangles in the graph.
+    pub fn number_of_triangles(&self) -> usize {
+        self.node_triangles.par_iter().sum::<usize>() / 3
+    }
+
+    /// Returns the number of triangles each node is part of.
+    #[inline(always)]
+    pub fn node_triangles(&self) -> &[usize] {
+        &self.node_triangles
+    }
+
+    /// Returns the local clustering coefficient of the provided node.
+    ///
+    /// Nodes with less than two neighbours have a clustering coefficient of zero.
+    #[inline(always)]
+    pub fn local_clustering_coefficient(&self, node: usize) -> f64 {
+        let degree = self.degrees[node];
+        if degree < 2 {
+            return 0.0;
+        }
+        2.0 * self.node_triangles[node] as f64 / (degree * (degree - 1)) as f64
+    }
+
+    /// Returns the local clustering coefficient of every node.
+    pub fn local_clustering_coefficients(&self) -> Vec<f64> {
+        (0..self.degrees.len())
+            .into_par_iter()
+            .map(|node| self.local_clustering_coefficient(node))
+            .collect()
+    }
+
+    /// Returns the mean of the local clustering coefficients.
+    pub fn average_clustering_coefficient(&self) -> f64 {
+        if self.degrees.is_empty() {
+            return 0.0;
+        }
+        (0..self.degrees.len())
+            .into_par_iter()
+            .map(|node| self.local_clustering_coefficient(node))
+            .sum::<f64>()
+            / self.degrees.len() as f64
+    }
+
+    /// Returns the transitivity of the graph, i.e. the fraction of the
+    /// wedges that are closed by a triangle.
+    pub fn transitivity(&self) -> f64 {
+        let wedges = self
+            .degrees
+            .par_iter()
+            .map(|&degree| degree * degree.saturating_sub(1) / 2)
+            .sum::<usize>();
+        if wedges == 0 {
+            return 0.0;
+        }
+        3.0 * self.number_of_triangles() as f64 / wedges as f64
+    }
+}
+
+/// Counts the triangles of the provided undirected graph.
+///
+/// # Arguments
+/// * `graph`: &G - The graph, with both directions of every edge and sorted successors.
+pub fn triangles<G>(graph: &G) -> Triangles
+where
+    G: Successors + Sync,
+    usize: To<G::Node>,
+{
+    let number_of_nodes = graph.number_of_nodes();
+
+    let degrees: Vec<usize> = (0..number_of_nodes)
+        .into_par_iter()
+        .map(|node| neighbours(graph, node.to()).count())
+        .collect();
+
+    let precedes = |src: usize, dst: usize| (degrees[src], src) < (degrees[dst], dst);
+
+    // We build the oriented graph, which is again sorted by node id as
+    // filtering the successors preserves their order.
+    let forward_degrees: Vec<usize> = (0..number_of_nodes)
+        .into_par_iter()
+        .map(|src| {
+            neighbours(graph, src.to())
+                .filter(|&dst| precedes(src, dst.to()))
+                .count()
+        })
+        .collect();
+
+    let mut offsets = Vec::with_capacity(number_of_nodes + 1);
+    offsets.push(0);
+    for forward_degree in forward_degrees {
+        offsets.push(offsets[offsets.len() - 1] + forward_degree);
+    }
+
+    let forward: Vec<G::Node> = (0..number_of_nodes)
+        .into_par_iter()
+        .flat_map_iter(|src| {
+            neighbours(graph, src.to()).filter(move |&dst| precedes(src, dst.to()))
+        })
+        .collect();
+
+    let node_triangles: Vec<AtomicUsize> =
+        (0..number_of_nodes).map(|_| AtomicUsize::new(0)).collect();
+
+    (0..number_of_nodes).into_par_iter().for_each(|src| {
+        let src_forward = &forward[offsets[src]..offsets[src + 1]];
+        let mut src_triangles = 0;
+        for &dst in src_forward {
+            let dst: usize = dst.to();
+            let dst_forward = &forward[offsets[dst]..offsets[dst + 1]];
+            let mut edge_triangles = 0;
+            for_each_common(src_forward, dst_forward, |third| {
+                let third: usize = third.to();
+                node_triangles[third].fetch_add(1, Ordering::Relaxed);
+                edge_triangles += 1;
+            });
+            if edge_triangles > 0 {
+                node_triangles[dst].fetch_add(edge_triangles, Ordering::Relaxed);
+                src_triangles += edge_triangles;
+            }
+        }
+        node_triangles[src].fetch_add(src_triangles, Ordering::Relaxed);
+    });
+
+    Triangles {
+        node_triangles: node_triangles
+            .into_iter()
+            .map(AtomicUsize::into_inner)
+            .collect(),
+        degrees,
+    }
+}
@@ -0,0 +1,122 @@
+//! Module providing helpers shared by the algorithms.
+use core::cmp::Ordering;
+use graph::Successors;
+use irontraits::To;
+use rand::Rng;
+
+/// Returns the successors of the node, skipping self-loops and duplicated edges.
+#[inline(always)]
+pub(crate) fn neighbours<G: Successors>(
+    graph: &G,
+    node: G::Node,
+) -> impl Iterator<Item = G::Node> + '_ {
+    let mut previous = None;
+    graph.successors(node).into_iter().filter(move |&dst| {
+        let is_new = dst != node && previous != Some(dst);
+        previous = Some(dst);
+        is_new
+    })
+}
+
+/// Calls the provided closure on every element shared by the two sorted slices.
+#[inline(always)]
+pub(crate) fn for_each_common<N: Ord + Copy>(left: &[N], right: &[N], mut callback: impl FnMut(N)) {
+    let (mut i, mut j) = (0, 0);
+    while i < left.len() && j < right.len() {
+        match left[i].cmp(&right[j]) {
+            Ordering::Less => i += 1,
+            Ordering::Greater => j += 1,
+            Ordering::Equal => {
+                callback(left[i]);
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+}
+
+/// Returns the number of successors of the node.
+///
+/// The size hint of the successors is used when exact, as it is the case
+/// for the CSR, to avoid iterating over them.
+#[inline(always)]
+pub(crate) fn degree<G: Successors>(graph: &G, node: G::Node) -> usize {
+    let successors = graph.successors(node).into_iter();
+    match successors.size_hint() {
+        (lower, Some(upper)) if lower == upper => lower,
+        _ => successors.count(),
+    }
+}
+
+/// Returns a successor of the node chosen uniformly at random, if any.
+#[inline(always)]
+pub(crate) fn random_successor<G: Successors, R: Rng>(
+    graph: &G,
+    node: G::Node,
+    rng: &mut R,
+) -> Option<G::Node> {
+    let degree = degree(graph, node);
+    if degree == 0 {
+        return None;
+    }
+    graph
+        .successors(node)
+        .into_iter()
+        .nth(rng.gen_range(0..degree))
+}
+
+/// Returns a well-mixed hash of the provided value, used to derive
+/// reproducible pseudo-random decisions from a seed.
+#[inline(always)]
+pub(crate) fn splitmix64(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Returns the index of the node, to be used to access per-node arrays.
+#[inline(always)]
+pub(crate) fn index<N: To<usize>>(node: N) -> usize {
+    node.to()
+}
+
+/// A union-find with path halving and union by size.
+pub(crate) struct DisjointSets {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+}
+
+impl DisjointSets {
+    pub(crate) fn new(number_of_elements: usize) -> Self {
+        DisjointSets {
+            parents: (0..number_of_elements).collect(),
+            sizes: vec![1; number_of_elements],
+        }
+    }
+
+    /// Returns the representative of the set of the element.
+    #[inline(always)]
+    pub(crate) fn find(&mut self, mut element: usize) -> usize {
+        while self.parents[element] != element {
+            self.parents[element] = self.parents[self.parents[element]];
+            element = self.parents[element];
+        }
+        element
+    }
+
+    /// Merges the sets of the two elements, and returns whether they were distinct.
+    #[inline(always)]
+    pub(crate) fn union(&mut self, left: usize, right: usize) -> bool {
+        let (mut left, mut right) = (self.find(left), self.find(right));
+        if left == right {
+            return false;
+        }
+        if self.sizes[left] < self.sizes[right] {
+            core::mem::swap(&mut left, &mut right);
+        }
+        self.parents[right] = left;
+        self.sizes[left] += self.sizes[right];
+        true
+    }
+}
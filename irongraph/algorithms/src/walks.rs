@@ -0,0 +1,201 @@
+//! Module providing parallel uniform and node2vec random walks.
+//!
+//! Every walk is generated with its own random number generator, seeded from
+//! the walk seed and the index of the walk, so that the walks do not depend
+//! on how they are scheduled across the threads. The generator is
+//! Xoshiro256++, whose output is the same on every platform and release of
+//! the crates, so that the walks of a seed are reproducible anywhere.
+use crate::utils::random_successor;
+use graph::Successors;
+use irontraits::To;
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+use rayon::prelude::*;
+
+/// Parameters of the random walks.
+///
+/// The walks are written in a flat buffer of shape `walks × walk_length`,
+/// where the first node of each walk is its source. A walk reaching a node
+/// without successors stays on it for the remaining steps.
+#[derive(Debug, Clone)]
+pub struct RandomWalks {
+    /// The number of nodes in each walk, source included.
+    walk_length: usize,
+    /// How many walks to start from each source.
+    walks_per_node: usize,
+    /// The node2vec return weight, i.e. the inverse of the parameter p.
+    return_weight: f64,
+    /// The node2vec in-out weight, i.e. the inverse of the parameter q.
+    explore_weight: f64,
+    /// The seed of the walks.
+    seed: u64,
+}
+
+impl Default for RandomWalks {
+    fn default() -> Self {
+        RandomWalks {
+            walk_length: 80,
+            walks_per_node: 1,
+            return_weight: 1.0,
+            explore_weight: 1.0,
+            seed: 0xf9e4_62d3_28e8_4142,
+        }
+    }
+}
+
+impl RandomWalks {
+    /// Sets the number of nodes in each walk, source included.
+    ///
+    /// # Panics
+    /// If the walk length is zero.
+    pub fn walk_length(mut self, walk_length: usize) -> Self {
+        assert!(walk_length > 0, "The walk length must be greater than 0.");
+        self.walk_length = walk_length;
+        self
+    }
+
+    /// Sets how many walks to start from each source.
+    pub fn walks_per_node(mut self, walks_per_node: usize) -> Self {
+        self.walks_per_node = walks_per_node;
+        self
+    }
+
+    /// Sets the node2vec parameters, turning the walks into second-order walks.
+    ///
+    /// # Arguments
+    /// * `p`: f64 - The return parameter, the higher the less likely to go back to the previous node.
+    /// * `q`: f64 - The in-out parameter, the higher the more likely to stay close to the previous node.
+    ///
+    /// # Panics
+    /// If either parameter is not strictly positive.
+    pub fn node2vec(mut self, p: f64, q: f64) -> Self {
+        assert!(p > 0.0, "The return parameter p must be positive.");
+        assert!(q > 0.0, "The in-out parameter q must be positive.");
+        self.return_weight = 1.0 / p;
+        self.explore_weight = 1.0 / q;
+        self
+    }
+
+    /// Sets the seed of the walks.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Returns whether the walks are second-order walks.
+    #[inline(always)]
+    pub fn is_second_order(&self) -> bool {
+        self.return_weight != 1.0 || self.explore_weight != 1.0
+    }
+
+    /// Returns `walks_per_node` walks from every node of the graph.
+    pub fn walks<G>(&self, graph: &G) -> Vec<G::Node>
+    where
+        G: Successors + Sync,
+        usize: To<G::Node>,
+    {
+        let sources: Vec<G::Node> = (0..graph.number_of_nodes()).map(|node| node.to()).collect();
+        self.walks_from(graph, &sources)
+    }
+
+    /// Returns `walks_per_node` walks from every provided source.
+    ///
+    /// The walk of index `i` starts from the source of index `i % sources.len()`.
+    pub fn walks_from<G>(&self, graph: &G, sources: &[G::Node]) -> Vec<G::Node>
+    where
+        G: Successors + Sync,
+    {
+        let mut target =
+            vec![G::Node::default(); sources.len() * self.walks_per_node * self.walk_length];
+        self.walks_from_into(graph, sources, &mut target);
+        target
+    }
+
+    /// Writes `walks_per_node` walks from every provided source into the target buffer.
+    ///
+    /// # Arguments
+    /// * `graph`: &G - The graph to walk on.
+    /// * `sources`: &[G::Node] - The nodes to start the walks from.
+    /// * `target`: &mut [G::Node] - The buffer of shape `sources.len() * walks_per_node × walk_length`.
+    ///
+    /// # Panics
+    /// If the target buffer does not have the expected length.
+    pub fn walks_from_into<G>(&self, graph: &G, sources: &[G::Node], target: &mut [G::Node])
+    where
+        G: Successors + Sync,
+    {
+        assert_eq!(
+            target.len(),
+            sources.len() * self.walks_per_node * self.walk_length,
+            "The target buffer must have shape walks × walk length."
+        );
+
+        target
+            .par_chunks_exact_mut(self.walk_length)
+            .enumerate()
+            .for_each(|(walk_index, walk)| {
+                let mut rng = Xoshiro256PlusPlus::seed_from_u64(
+                    self.seed
+                        .wrapping_add(walk_index as u64)
+                        .wrapping_mul(0x9e37_79b9_7f4a_7c15),
+                );
+                self.walk(graph, sources[walk_index % sources.len()], &mut rng, walk);
+            });
+    }
+
+    fn walk<G: Successors, R: Rng>(
+        &self,
+        graph: &G,
+        source: G::Node,
+        rng: &mut R,
+        walk: &mut [G::Node],
+    ) {
+        walk[0] = source;
+        let mut previous = None;
+        for step in 1..walk.len() {
+            let current = walk[step - 1];
+            let next = match previous {
+                Some(previous) if self.is_second_order() => {
+                    self.second_order_step(graph, previous, current, rng)
+                }
+                _ => random_successor(graph, current, rng),
+            };
+            match next {
+                Some(next) => {
+                    walk[step] = next;
+                    previous = Some(current);
+                }
+                None => {
+                    walk[step..].fill(current);
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Returns the next node of a node2vec walk, using rejection sampling
+    /// so that the weights of the successors are never materialized.
+    #[inline(always)]
+    fn second_order_step<G: Successors, R: Rng>(
+        &self,
+        graph: &G,
+        previous: G::Node,
+        current: G::Node,
+        rng: &mut R,
+    ) -> Option<G::Node> {
+        let maximum_weight = self.return_weight.max(self.explore_weight).max(1.0);
+        loop {
+            let candidate = random_successor(graph, current, rng)?;
+            let weight = if candidate == previous {
+                self.return_weight
+            } else if graph.has_successor(previous, candidate) {
+                1.0
+            } else {
+                self.explore_weight
+            };
+            if rng.gen::<f64>() * maximum_weight < weight {
+                return Some(candidate);
+            }
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use algorithms::prelude::*;
+use graph::*;
+
+mod common;
+use common::undirected_csr;
+
+#[test]
+fn test_bipartition_and_projections() {
+    // Users 0, 1, 2 and items 3, 4, 5, 6, with user 2 only rating item 6.
+    let csr = undirected_csr(7, &[(0, 3), (0, 4), (1, 3), (1, 4), (1, 5), (2, 6)]);
+
+    let bipartition = bipartition(&csr).unwrap();
+    assert!(is_bipartite(&csr));
+    assert_eq!(bipartition.nodes::<usize>(Side::Left), vec![0, 1, 2]);
+    assert_eq!(bipartition.nodes::<usize>(Side::Right), vec![3, 4, 5, 6]);
+    for src in 0..csr.number_of_nodes() {
+        for dst in csr.successors(src) {
+            assert_ne!(bipartition.side(src), bipartition.side(dst));
+        }
+    }
+
+    let users = bipartition.projection(&csr, Side::Left);
+    assert_eq!(users.number_of_nodes(), 3);
+    assert_eq!(
+        users.weighted_successors(0).collect::<Vec<_>>(),
+        vec![(1, 2)]
+    );
+    assert_eq!(
+        users.weighted_successors(1).collect::<Vec<_>>(),
+        vec![(0, 2)]
+    );
+    assert_eq!(users.weighted_successors(2).count(), 0);
+
+    let items = bipartition.projection(&csr, Side::Right);
+    assert_eq!(items.number_of_nodes(), 4);
+    assert_eq!(
+        items.weighted_successors(0).collect::<Vec<_>>(),
+        vec![(1, 2), (2, 1)]
+    );
+    assert_eq!(
+        items.weighted_successors(2).collect::<Vec<_>>(),
+        vec![(0, 1), (1, 1)]
+    );
+    assert_eq!(items.weighted_successors(3).count(), 0);
+}
+
+#[test]
+fn test_odd_cycle_witness() {
+    // An even cycle 0..4 with a chord closing the odd cycle 0, 1, 2, 5, 6.
+    let csr = undirected_csr(7, &[(0, 1), (1, 2), (2, 3), (3, 0), (2, 5), (5, 6), (6, 0)]);
+
+    let cycle = bipartition(&csr).unwrap_err();
+    let nodes = cycle.nodes();
+    assert_eq!(nodes.len() % 2, 1);
+    for (position, &src) in nodes.iter().enumerate() {
+        let dst = nodes[(position + 1) % nodes.len()];
+        assert!(csr.has_successor(src, dst));
+    }
+    assert!(!is_bipartite(&csr));
+
+    let self_loop = undirected_csr(2, &[(0, 1), (1, 1)]);
+    assert_eq!(bipartition(&self_loop).unwrap_err().nodes(), &[1]);
+}
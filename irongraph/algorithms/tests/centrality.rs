@@ -0,0 +1,57 @@
+use algorithms::prelude::*;
+
+mod common;
+use common::undirected_csr;
+
+fn assert_close(left: &[f64], right: &[f64], tolerance: f64) {
+    assert_eq!(left.len(), right.len());
+    for (left, right) in left.iter().zip(right) {
+        assert!((left - right).abs() <= tolerance, "{} != {}", left, right);
+    }
+}
+
+#[test]
+fn test_exact_betweenness() {
+    let path = undirected_csr(5, &[(0, 1), (1, 2), (2, 3), (3, 4)]);
+    let scores = Betweenness::default().scores(&path);
+    assert_eq!(scores.number_of_sources(), 5);
+    assert_close(scores.scores(), &[0.0, 0.5, 2.0 / 3.0, 0.5, 0.0], 1e-12);
+
+    // The center of a star lies on the only path between any two leaves.
+    let star = undirected_csr(5, &[(0, 1), (0, 2), (0, 3), (0, 4)]);
+    assert_close(
+        Betweenness::default().scores(&star).scores(),
+        &[1.0, 0.0, 0.0, 0.0, 0.0],
+        1e-12,
+    );
+}
+
+#[test]
+fn test_sampled_betweenness() {
+    let number_of_nodes = 200;
+    let edges = (0..number_of_nodes)
+        .flat_map(|src| ((src + 1)..number_of_nodes).map(move |dst| (src, dst)))
+        .filter(|(src, dst)| dst - src == 1 || (src * 31 + dst * 17 + src * dst) % 37 == 0)
+        .collect::<Vec<_>>();
+    let csr = undirected_csr(number_of_nodes, &edges);
+    let exact = Betweenness::default().scores(&csr);
+
+    let sampled = Betweenness::default()
+        .number_of_sources(2000)
+        .seed(7)
+        .scores(&csr);
+    assert_eq!(sampled.number_of_sources(), 2000);
+    assert_close(sampled.scores(), exact.scores(), 0.05);
+    assert_eq!(
+        sampled.scores(),
+        Betweenness::default()
+            .number_of_sources(2000)
+            .seed(7)
+            .scores(&csr)
+            .scores()
+    );
+
+    let bounded = Betweenness::default().error_bound(0.05, 0.1).scores(&csr);
+    assert!(bounded.number_of_sources() > 0);
+    assert_close(bounded.scores(), exact.scores(), 0.05);
+}
@@ -0,0 +1,32 @@
+//! Fixtures shared by the integration tests of the algorithms.
+#![allow(dead_code)]
+
+use csr::prelude::*;
+
+/// Returns the CSR with the provided edges, sorted and deduplicated.
+pub fn directed_csr(
+    number_of_nodes: usize,
+    edges: &[(usize, usize)],
+) -> CSR<Vec<usize>, Vec<usize>> {
+    let mut edges = edges.to_vec();
+    edges.sort();
+    edges.dedup();
+
+    CSRBuilder::default()
+        .number_of_edges(edges.len())
+        .number_of_nodes(number_of_nodes)
+        .sorted()
+        .build(edges)
+}
+
+/// Returns the CSR with the provided edges in both directions.
+pub fn undirected_csr(
+    number_of_nodes: usize,
+    edges: &[(usize, usize)],
+) -> CSR<Vec<usize>, Vec<usize>> {
+    let edges = edges
+        .iter()
+        .flat_map(|&(src, dst)| [(src, dst), (dst, src)])
+        .collect::<Vec<_>>();
+    directed_csr(number_of_nodes, &edges)
+}
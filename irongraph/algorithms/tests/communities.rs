@@ -0,0 +1,72 @@
+use algorithms::prelude::*;
+use csr::prelude::*;
+
+mod common;
+use common::undirected_csr;
+
+/// Two cliques of five nodes connected by a single edge.
+fn barbell() -> CSR<Vec<usize>, Vec<usize>> {
+    let mut edges = Vec::new();
+    for offset in [0, 5] {
+        for src in 0..5 {
+            for dst in (src + 1)..5 {
+                edges.push((offset + src, offset + dst));
+            }
+        }
+    }
+    edges.push((4, 5));
+    undirected_csr(10, &edges)
+}
+
+#[test]
+fn test_label_propagation() {
+    let csr = barbell();
+    let communities = LabelPropagation::default().seed(3).communities(&csr);
+
+    assert_eq!(communities.number_of_communities(), 2);
+    assert_eq!(communities.communities(), &[0, 0, 0, 0, 0, 1, 1, 1, 1, 1]);
+    assert_eq!(communities.community_sizes(), vec![5, 5]);
+    assert!((communities.modularity() - modularity(&csr, communities.communities())).abs() < 1e-12);
+
+    let repeated = LabelPropagation::default().seed(3).communities(&csr);
+    assert_eq!(communities.communities(), repeated.communities());
+}
+
+#[test]
+fn test_louvain() {
+    let csr = barbell();
+    let communities = Louvain::default().seed(5).communities(&csr);
+
+    assert_eq!(communities.number_of_communities(), 2);
+    assert_eq!(communities.communities(), &[0, 0, 0, 0, 0, 1, 1, 1, 1, 1]);
+    // Each clique has 20 of the 42 stored edges inside, and degrees summing to 21.
+    let expected = 2.0 * (20.0 / 42.0 - (21.0f64 / 42.0).powi(2));
+    assert!((communities.modularity() - expected).abs() < 1e-12);
+}
+
+#[test]
+fn test_weighted_louvain() {
+    // A cycle of four nodes, where the heavy edges define the communities.
+    let mut edges: Vec<(usize, usize, f64)> =
+        [(0, 1, 10.0), (1, 2, 1.0), (2, 3, 10.0), (3, 0, 1.0)]
+            .into_iter()
+            .flat_map(|(src, dst, weight)| [(src, dst, weight), (dst, src, weight)])
+            .collect();
+    edges.sort_by_key(|edge| (edge.0, edge.1));
+    let csr: WeightedCSR<Vec<usize>, Vec<usize>, Vec<f64>> = CSRBuilder::default()
+        .number_of_edges(edges.len())
+        .number_of_nodes(4)
+        .sorted()
+        .build_weighted(edges);
+
+    let communities = Louvain::default().weighted_communities(&csr);
+    assert_eq!(communities.communities(), &[0, 0, 1, 1]);
+    assert!(
+        (communities.modularity() - weighted_modularity(&csr, communities.communities())).abs()
+            < 1e-12
+    );
+    assert!(communities.modularity() > modularity(csr.csr(), communities.communities()));
+
+    let communities = LabelPropagation::default().weighted_communities(&csr);
+    assert_eq!(communities.communities(), &[0, 0, 1, 1]);
+}
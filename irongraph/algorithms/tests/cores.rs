@@ -0,0 +1,71 @@
+use algorithms::prelude::*;
+use csr::prelude::*;
+use graph::*;
+
+mod common;
+use common::undirected_csr;
+
+/// Checks that every node has at most `degeneracy` neighbours following it in the ordering.
+fn assert_degeneracy_ordering(csr: &CSR<Vec<usize>, Vec<usize>>, cores: &CoreDecomposition<usize>) {
+    let mut positions = vec![usize::MAX; csr.number_of_nodes()];
+    for (position, &node) in cores.degeneracy_ordering().iter().enumerate() {
+        assert_eq!(positions[node], usize::MAX, "node {} appears twice", node);
+        positions[node] = position;
+    }
+    for node in 0..csr.number_of_nodes() {
+        let later_neighbours = csr
+            .successors(node)
+            .into_iter()
+            .filter(|&neighbour| positions[neighbour] > positions[node])
+            .count();
+        assert!(later_neighbours <= cores.degeneracy());
+    }
+}
+
+#[test]
+fn test_core_decomposition() {
+    // A clique of four nodes with a triangle attached to node 2, a path of
+    // two nodes hanging from node 3 and an isolated node.
+    let csr = undirected_csr(
+        10,
+        &[
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 2),
+            (1, 3),
+            (2, 3),
+            (2, 5),
+            (3, 4),
+            (4, 9),
+            (5, 6),
+            (5, 7),
+            (6, 7),
+        ],
+    );
+    let expected = [3, 3, 3, 3, 1, 2, 2, 2, 0, 1];
+
+    for cores in [core_decomposition(&csr), par_core_decomposition(&csr)] {
+        assert_eq!(cores.core_numbers(), &expected);
+        assert_eq!(cores.degeneracy(), 3);
+        assert_eq!(cores.k_core(2), vec![0, 1, 2, 3, 5, 6, 7]);
+        assert_degeneracy_ordering(&csr, &cores);
+    }
+}
+
+#[test]
+fn test_parallel_core_decomposition_matches_sequential() {
+    let number_of_nodes = 200;
+    let edges = (0..number_of_nodes)
+        .flat_map(|src| ((src + 1)..number_of_nodes).map(move |dst| (src, dst)))
+        .filter(|(src, dst)| (src * 31 + dst * 17 + src * dst) % 23 < 2)
+        .collect::<Vec<_>>();
+    let csr = undirected_csr(number_of_nodes, &edges);
+
+    let sequential = core_decomposition(&csr);
+    let parallel = par_core_decomposition(&csr);
+
+    assert_eq!(sequential.core_numbers(), parallel.core_numbers());
+    assert_degeneracy_ordering(&csr, &sequential);
+    assert_degeneracy_ordering(&csr, &parallel);
+}
@@ -0,0 +1,64 @@
+use algorithms::prelude::*;
+use graph::*;
+
+mod common;
+use common::directed_csr;
+
+#[test]
+fn test_topological_sort() {
+    // A diamond from 0 to 3, a chain from 3 to 5 and shortcuts 0 -> 3 and 1 -> 5.
+    let csr = directed_csr(
+        7,
+        &[
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 3),
+            (1, 5),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+        ],
+    );
+    let order = topological_sort(&csr).unwrap();
+
+    let mut positions = [0; 7];
+    for (position, &node) in order.order().iter().enumerate() {
+        positions[node] = position;
+    }
+    for src in 0..7 {
+        for dst in csr.successors(src) {
+            assert!(positions[src] < positions[dst]);
+        }
+    }
+
+    assert_eq!(order.number_of_levels(), 5);
+    assert_eq!(order.level(0), &[0, 6]);
+    assert_eq!(order.depths(), &[0, 1, 1, 2, 3, 4, 0]);
+    let longest_path = order.longest_path();
+    assert_eq!(longest_path.len(), 5);
+    assert_eq!(longest_path[0], 0);
+    assert_eq!(&longest_path[2..], &[3, 4, 5]);
+    assert!(is_acyclic(&csr));
+
+    let reduction = transitive_reduction(&csr).unwrap();
+    assert_eq!(reduction.number_of_edges(), 6);
+    assert!(!reduction.has_successor(0, 3));
+    assert!(!reduction.has_successor(1, 5));
+    assert!(reduction.has_successor(1, 3));
+}
+
+#[test]
+fn test_cycle_detection() {
+    // A chain leading into the cycle 2 -> 3 -> 4 -> 2.
+    let csr = directed_csr(6, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 2), (4, 5)]);
+
+    let cycle = topological_sort(&csr).unwrap_err();
+    assert_eq!(cycle.nodes(), &[2, 3, 4]);
+    assert_eq!(find_cycle(&csr), Some(cycle));
+    assert!(!is_acyclic(&csr));
+    assert!(transitive_reduction(&csr).is_err());
+
+    let self_loop = directed_csr(2, &[(0, 1), (1, 1)]);
+    assert_eq!(find_cycle(&self_loop).unwrap().nodes(), &[1]);
+}
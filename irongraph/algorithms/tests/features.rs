@@ -0,0 +1,38 @@
+use algorithms::prelude::*;
+use rayon::prelude::*;
+
+/// The sum and the difference of the endpoints of the edges.
+struct Arithmetic;
+
+impl EdgeFeatures for Arithmetic {
+    type Node = usize;
+
+    fn feature_len(&self) -> usize {
+        2
+    }
+
+    fn feature_names(&self) -> Vec<String> {
+        vec!["sum".to_string(), "difference".to_string()]
+    }
+
+    fn edge_features(&self, (src, dst): (usize, usize), target: &mut [f32]) {
+        assert!(target.iter().all(|feature| *feature == 0.0));
+        target[0] = (src + dst) as f32;
+        target[1] = src as f32 - dst as f32;
+    }
+}
+
+#[test]
+fn test_par_edge_features() {
+    let edges = (0..1000).map(|src| (src, 2 * src)).collect::<Vec<_>>();
+    let features = Arithmetic.par_edge_features_matrix(edges.par_iter().copied());
+    assert_eq!(features.len(), 2000);
+    for (row, (src, dst)) in features.chunks_exact(2).zip(edges.iter()) {
+        assert_eq!(row, [(src + dst) as f32, *src as f32 - *dst as f32]);
+    }
+
+    // The rows are zeroed before being written.
+    let mut target = vec![1.0; 2000];
+    Arithmetic.par_edge_features(edges, &mut target);
+    assert_eq!(target, features);
+}
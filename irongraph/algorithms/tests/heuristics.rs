@@ -0,0 +1,103 @@
+use algorithms::prelude::*;
+use graph::*;
+use rayon::prelude::*;
+
+mod common;
+use common::{directed_csr, undirected_csr};
+
+#[test]
+fn test_heuristics_clique_and_path() {
+    // A clique of four nodes, with a path hanging from node 3.
+    let edges = [
+        (0, 1),
+        (0, 2),
+        (0, 3),
+        (1, 2),
+        (1, 3),
+        (2, 3),
+        (3, 4),
+        (4, 5),
+    ];
+    let graph = undirected_csr(6, &edges);
+    let scores = LinkPrediction::default().katz_beta(0.5).scores(&graph);
+
+    assert_eq!(scores.feature_len(), 6);
+    assert_eq!(scores.feature_names()[0], "common_neighbours");
+    assert_eq!(scores.feature_names()[5], "katz");
+    assert_eq!(
+        scores.par_edge_features_matrix(vec![(0, 4), (0, 5)]),
+        vec![
+            1.0,
+            0.25,
+            1.0 / 4.0_f64.ln() as f32,
+            0.25,
+            6.0,
+            // One walk of length two and two walks of length three.
+            0.25 + 0.125 * 2.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            3.0,
+            0.125,
+        ]
+    );
+}
+
+#[test]
+fn test_katz_against_matrix_powers() {
+    let number_of_nodes = 12;
+    let edges: Vec<(usize, usize)> = (0..number_of_nodes)
+        .flat_map(|src| (0..number_of_nodes).map(move |dst| (src, dst)))
+        .filter(|&(src, dst)| src != dst && (src * 7 + dst * 3) % 5 == 0)
+        .collect();
+    let graph = directed_csr(number_of_nodes, &edges);
+
+    let mut adjacency = vec![vec![0.0_f64; number_of_nodes]; number_of_nodes];
+    for &(src, dst) in &edges {
+        adjacency[src][dst] = 1.0;
+    }
+
+    let (hops, beta) = (4, 0.3);
+    let mut expected = vec![vec![0.0_f64; number_of_nodes]; number_of_nodes];
+    let mut power = adjacency.clone();
+    let mut damping = beta;
+    for _ in 0..hops {
+        for (expected_row, power_row) in expected.iter_mut().zip(&power) {
+            for (expected, walks) in expected_row.iter_mut().zip(power_row) {
+                *expected += damping * walks;
+            }
+        }
+        power = power
+            .iter()
+            .map(|row| {
+                (0..number_of_nodes)
+                    .map(|dst| {
+                        (0..number_of_nodes)
+                            .map(|mid| row[mid] * adjacency[mid][dst])
+                            .sum()
+                    })
+                    .collect()
+            })
+            .collect();
+        damping *= beta;
+    }
+
+    let pairs: Vec<(usize, usize)> = (0..number_of_nodes)
+        .flat_map(|src| (0..number_of_nodes).map(move |dst| (src, dst)))
+        .collect();
+    let scores = LinkPrediction::default()
+        .katz_hops(hops)
+        .katz_beta(beta)
+        .scores(&graph)
+        .par_edge_features_matrix(pairs.par_iter().copied());
+
+    for (row, &(src, dst)) in scores.chunks_exact(6).zip(&pairs) {
+        assert!((row[5] as f64 - expected[src][dst]).abs() < 1e-5);
+        assert_eq!(
+            row[4],
+            (graph.successors(src).into_iter().count() * graph.successors(dst).into_iter().count())
+                as f32
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use algorithms::prelude::*;
+use csr::generators::*;
+use csr::prelude::*;
+use graph::*;
+
+fn edges(graph: &VecCSR<usize>) -> Vec<(usize, usize)> {
+    (0..graph.number_of_nodes())
+        .flat_map(|src| graph.successors(src).into_iter().map(move |dst| (src, dst)))
+        .collect()
+}
+
+/// Checks that the splits partition the edges of the graph, keeping both
+/// directions of each edge in the same split.
+fn assert_partition(graph: &VecCSR<usize>, split: &EdgeSplit<usize>) {
+    let mut union = edges(split.train());
+    union.extend(edges(split.test()));
+    union.sort();
+    assert_eq!(union, edges(graph));
+    for (src, dst) in edges(split.test()) {
+        assert!(split.test().has_successor(dst, src));
+        assert!(!split.train().has_successor(src, dst));
+    }
+}
+
+#[test]
+fn test_uniform_and_connected_holdouts() {
+    let graph: VecCSR<usize> = erdos_renyi_gnm(100, 400, 42);
+
+    let holdout = Holdout::default().test_fraction(0.25).seed(7);
+    let uniform = holdout.uniform(&graph).unwrap();
+    assert_partition(&graph, &uniform);
+    assert_eq!(uniform.test().number_of_edges(), 200);
+    assert_eq!(
+        edges(uniform.test()),
+        edges(
+            Holdout::default()
+                .test_fraction(0.25)
+                .seed(7)
+                .uniform(&graph)
+                .unwrap()
+                .test()
+        )
+    );
+
+    // A tree has no edge that can be held out without disconnecting it.
+    let tree: VecCSR<usize> = path(20);
+    let connected = holdout.connected(&tree).unwrap();
+    assert_eq!(connected.test().number_of_edges(), 0);
+    assert_eq!(connected.train().number_of_edges(), tree.number_of_edges());
+
+    let connected = holdout.connected(&graph).unwrap();
+    assert_partition(&graph, &connected);
+    assert_eq!(connected.test().number_of_edges(), 200);
+    let components = |graph: &VecCSR<usize>| {
+        let mut reached = vec![false; graph.number_of_nodes()];
+        let mut components = 0;
+        for root in 0..graph.number_of_nodes() {
+            if reached[root] {
+                continue;
+            }
+            components += 1;
+            reached[root] = true;
+            let mut stack = vec![root];
+            while let Some(node) = stack.pop() {
+                for dst in graph.successors(node) {
+                    if !reached[dst] {
+                        reached[dst] = true;
+                        stack.push(dst);
+                    }
+                }
+            }
+        }
+        components
+    };
+    assert_eq!(components(connected.train()), components(&graph));
+}
+
+#[test]
+fn test_k_fold() {
+    let graph: VecCSR<usize> = erdos_renyi_gnm(50, 101, 42);
+    let folds = Holdout::default().k_fold(&graph, 4).unwrap();
+    assert_eq!(folds.len(), 4);
+
+    let mut tests = Vec::new();
+    for fold in folds.iter() {
+        assert_partition(&graph, fold);
+        let size = fold.test().number_of_edges() / 2;
+        assert!((25..=26).contains(&size));
+        tests.extend(edges(fold.test()));
+    }
+    tests.sort();
+    assert_eq!(tests, edges(&graph));
+}
+
+#[test]
+fn test_holdouts_of_directed_graphs() {
+    let graph: VecCSR<usize> = rmat(7, 500, [0.57, 0.19, 0.19], 42);
+    let holdout = Holdout::default().test_fraction(0.25);
+
+    // The undirected holdouts refuse the edges without their reverse.
+    let asymmetric_edge = holdout.uniform(&graph).unwrap_err();
+    assert!(graph.has_successor(asymmetric_edge.src(), asymmetric_edge.dst()));
+    assert!(!graph.has_successor(asymmetric_edge.dst(), asymmetric_edge.src()));
+    assert!(holdout.connected(&graph).is_err());
+    assert!(holdout.k_fold(&graph, 3).is_err());
+
+    let split = holdout.directed(true).uniform(&graph).unwrap();
+    let mut union = edges(split.train());
+    union.extend(edges(split.test()));
+    union.sort();
+    assert_eq!(union, edges(&graph));
+}
@@ -0,0 +1,74 @@
+use algorithms::prelude::*;
+use csr::generators::*;
+use csr::prelude::*;
+use graph::*;
+
+fn distances_from(graph: &VecCSR<usize>, src: usize) -> Vec<usize> {
+    let mut distances = vec![usize::MAX; graph.number_of_nodes()];
+    distances[src] = 0;
+    let mut frontier = vec![src];
+    while !frontier.is_empty() {
+        let mut next = Vec::new();
+        for node in frontier {
+            for dst in graph.successors(node) {
+                if distances[dst] == usize::MAX {
+                    distances[dst] = distances[node] + 1;
+                    next.push(dst);
+                }
+            }
+        }
+        frontier = next;
+    }
+    distances
+}
+
+#[test]
+fn test_negative_sampling() {
+    let graph: VecCSR<usize> = erdos_renyi_gnm(200, 600, 42);
+    let split = Holdout::default().seed(3).uniform(&graph).unwrap();
+
+    for sampler in [
+        NegativeSampler::default(),
+        NegativeSampler::default().degree_biased(),
+        NegativeSampler::default().hard(3),
+    ] {
+        let negatives = sampler.sample_excluding(split.train(), split.test(), 1000);
+        assert_eq!(negatives.len(), 1000);
+        assert_eq!(
+            negatives,
+            sampler.sample_excluding(split.train(), split.test(), 1000)
+        );
+        for &(src, dst) in negatives.iter() {
+            assert_ne!(src, dst);
+            assert!(!graph.has_successor(src, dst));
+        }
+    }
+
+    let hard = NegativeSampler::default().hard(3).sample(&graph, 200);
+    for (src, dst) in hard {
+        assert!((2..=3).contains(&distances_from(&graph, src)[dst]));
+    }
+
+    // The isolated nodes of a star with extra nodes are never sampled by degree.
+    let mut edges = (1..10)
+        .flat_map(|leaf| [(0, leaf), (leaf, 0)])
+        .collect::<Vec<_>>();
+    edges.sort();
+    let star: VecCSR<usize> = CSRBuilder::default()
+        .number_of_edges(edges.len())
+        .number_of_nodes(20)
+        .sorted()
+        .build(edges);
+    let negatives = NegativeSampler::default()
+        .degree_biased()
+        .sample(&star, 100);
+    assert_eq!(negatives.len(), 100);
+    assert!(negatives.iter().all(|&(src, dst)| src < 10 && dst < 10));
+
+    // A clique has no negative edges at all.
+    let clique: VecCSR<usize> = clique(5);
+    assert!(NegativeSampler::default()
+        .maximum_attempts(10)
+        .sample(&clique, 10)
+        .is_empty());
+}
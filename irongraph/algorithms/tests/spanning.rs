@@ -0,0 +1,85 @@
+use algorithms::prelude::*;
+use csr::prelude::*;
+use graph::*;
+
+fn undirected_weighted_csr(
+    number_of_nodes: usize,
+    edges: &[(usize, usize, f64)],
+) -> VecWeightedCSR<usize, f64> {
+    let mut edges = edges
+        .iter()
+        .flat_map(|&(src, dst, weight)| [(src, dst, weight), (dst, src, weight)])
+        .collect::<Vec<_>>();
+    edges.sort_by_key(|&(src, dst, _)| (src, dst));
+
+    CSRBuilder::default()
+        .number_of_edges(edges.len())
+        .number_of_nodes(number_of_nodes)
+        .sorted()
+        .build_weighted(edges)
+}
+
+fn forest_edges(forest: &SpanningForest<usize, f64>) -> Vec<(usize, usize, f64)> {
+    let forest = forest.forest();
+    (0..forest.number_of_nodes())
+        .flat_map(|src| {
+            forest
+                .weighted_successors(src)
+                .map(move |(dst, weight)| (src, dst, weight))
+        })
+        .collect()
+}
+
+#[test]
+fn test_minimum_spanning_forest() {
+    // A square with a diagonal, a separate weighted triangle and an isolated node.
+    let csr = undirected_weighted_csr(
+        8,
+        &[
+            (0, 1, 1.0),
+            (1, 2, 2.0),
+            (2, 3, 1.5),
+            (0, 3, 3.0),
+            (0, 2, 2.5),
+            (4, 5, 4.0),
+            (5, 6, 1.0),
+            (4, 6, 0.5),
+        ],
+    );
+    let expected = vec![
+        (0, 1, 1.0),
+        (1, 0, 1.0),
+        (1, 2, 2.0),
+        (2, 1, 2.0),
+        (2, 3, 1.5),
+        (3, 2, 1.5),
+        (4, 6, 0.5),
+        (5, 6, 1.0),
+        (6, 4, 0.5),
+        (6, 5, 1.0),
+    ];
+
+    for forest in [kruskal(&csr), boruvka(&csr)] {
+        assert_eq!(forest.total_weight(), 6.0);
+        assert_eq!(forest.forest().number_of_nodes(), 8);
+        assert_eq!(forest_edges(&forest), expected);
+    }
+}
+
+#[test]
+fn test_boruvka_matches_kruskal() {
+    let number_of_nodes = 300;
+    // Many equal weights, so that the ties must be broken consistently.
+    let edges = (0..number_of_nodes)
+        .flat_map(|src| ((src + 1)..number_of_nodes).map(move |dst| (src, dst)))
+        .filter(|(src, dst)| (src * 31 + dst * 17 + src * dst) % 29 < 2)
+        .map(|(src, dst)| (src, dst, ((src * 7 + dst * 13) % 5) as f64))
+        .collect::<Vec<_>>();
+    let csr = undirected_weighted_csr(number_of_nodes, &edges);
+
+    let kruskal = kruskal(&csr);
+    let boruvka = boruvka(&csr);
+
+    assert_eq!(kruskal.total_weight(), boruvka.total_weight());
+    assert_eq!(forest_edges(&kruskal), forest_edges(&boruvka));
+}
@@ -0,0 +1,61 @@
+use algorithms::prelude::*;
+use graph::*;
+
+mod common;
+use common::undirected_csr;
+
+#[test]
+fn test_triangles_clique_and_path() {
+    // A clique of four nodes, with a path hanging from node 3.
+    let csr = undirected_csr(
+        6,
+        &[
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 2),
+            (1, 3),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+        ],
+    );
+    let triangles = triangles(&csr);
+
+    assert_eq!(triangles.number_of_triangles(), 4);
+    assert_eq!(triangles.node_triangles(), &[3, 3, 3, 3, 0, 0]);
+    assert_eq!(triangles.local_clustering_coefficient(0), 1.0);
+    assert_eq!(triangles.local_clustering_coefficient(3), 0.5);
+    assert_eq!(triangles.local_clustering_coefficient(5), 0.0);
+    assert_eq!(triangles.transitivity(), 12.0 / 16.0);
+}
+
+#[test]
+fn test_triangles_against_brute_force() {
+    let number_of_nodes = 30;
+    let edges = (0..number_of_nodes)
+        .flat_map(|src| ((src + 1)..number_of_nodes).map(move |dst| (src, dst)))
+        .filter(|(src, dst)| (src * 31 + dst * 17 + src * dst) % 7 < 2)
+        .collect::<Vec<_>>();
+    let csr = undirected_csr(number_of_nodes, &edges);
+    let triangles = triangles(&csr);
+
+    let mut expected = vec![0; number_of_nodes];
+    for a in 0..number_of_nodes {
+        for b in (a + 1)..number_of_nodes {
+            for c in (b + 1)..number_of_nodes {
+                if csr.has_successor(a, b) && csr.has_successor(b, c) && csr.has_successor(a, c) {
+                    expected[a] += 1;
+                    expected[b] += 1;
+                    expected[c] += 1;
+                }
+            }
+        }
+    }
+
+    assert_eq!(triangles.node_triangles(), expected.as_slice());
+    assert_eq!(
+        triangles.number_of_triangles(),
+        expected.iter().sum::<usize>() / 3
+    );
+}
@@ -0,0 +1,64 @@
+use algorithms::prelude::*;
+use graph::*;
+
+mod common;
+use common::undirected_csr;
+
+#[test]
+fn test_uniform_walks() {
+    // A cycle of five nodes, a pendant node and an isolated node.
+    let csr = undirected_csr(7, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0), (4, 5)]);
+    let walks = RandomWalks::default()
+        .walk_length(10)
+        .walks_per_node(3)
+        .seed(42);
+
+    let result = walks.walks(&csr);
+    assert_eq!(result.len(), 7 * 3 * 10);
+    assert_eq!(result, walks.walks(&csr), "the walks must be deterministic");
+    assert_ne!(result, walks.clone().seed(43).walks(&csr));
+
+    for (walk_index, walk) in result.chunks_exact(10).enumerate() {
+        assert_eq!(walk[0], walk_index % 7);
+        if walk[0] == 6 {
+            assert!(walk.iter().all(|&node| node == 6));
+            continue;
+        }
+        for step in walk.windows(2) {
+            assert!(csr.has_successor(step[0], step[1]));
+        }
+    }
+}
+
+#[test]
+fn test_node2vec_walks() {
+    let csr = undirected_csr(5, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)]);
+
+    // With a huge return parameter, walks on a cycle never turn back.
+    let walks = RandomWalks::default()
+        .walk_length(12)
+        .walks_per_node(4)
+        .node2vec(1e12, 1.0)
+        .seed(7);
+    assert!(walks.is_second_order());
+
+    let result = walks.walks(&csr);
+    assert_eq!(result, walks.walks(&csr));
+    for walk in result.chunks_exact(12) {
+        for step in walk.windows(3) {
+            assert!(csr.has_successor(step[0], step[1]));
+            assert_ne!(step[0], step[2]);
+        }
+    }
+}
+
+#[test]
+fn test_walks_are_reproducible() {
+    // The walks of a seed must not change across platforms and releases.
+    let csr = undirected_csr(4, &[(0, 1), (1, 2), (2, 3), (3, 0)]);
+    let walks = RandomWalks::default()
+        .walk_length(8)
+        .walks_per_node(1)
+        .seed(42);
+    assert_eq!(walks.walks_from(&csr, &[0]), vec![0, 1, 2, 3, 2, 3, 2, 1]);
+}
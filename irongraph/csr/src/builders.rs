@@ -1,4 +1,5 @@
 use crate::csr::CSR;
+use crate::weighted::WeightedCSR;
 use core::marker::PhantomData;
 use irontraits::{Iter, IterMut, One, PositiveInteger, SequenceAllocable, Zero};
 
@@ -144,4 +145,25 @@ where
         let destinations = unsafe { Destinations::uninitialized(self.number_of_edges) };
         self.build_from(edges_iter, offsets, destinations)
     }
+
+    pub fn build_weighted<Weights, I>(
+        self,
+        edges_iter: I,
+    ) -> WeightedCSR<Destinations, Offsets, Weights>
+    where
+        Weights: SequenceAllocable + IterMut,
+        I: IntoIterator<Item = (Destinations::Item, Destinations::Item, Weights::Item)>,
+    {
+        let mut weights = unsafe { Weights::uninitialized(self.number_of_edges) };
+        let csr = {
+            let mut weights_iter = weights.iter_mut();
+            self.build(edges_iter.into_iter().map(|(src, dst, weight)| {
+                if let Some(target_weight) = weights_iter.next() {
+                    *target_weight = weight;
+                }
+                (src, dst)
+            }))
+        };
+        unsafe { WeightedCSR::new(csr, weights) }
+    }
 }
@@ -4,12 +4,19 @@ use graph::{Graph, Successors};
 use ironstructs::{custom_iters::copied::Copied, ranger::Ranger};
 use irontraits::{PositiveInteger, SequenceLen, SequenceRandomAccess, To};
 
-#[derive(Epserde, Debug, Clone)]
+/// A graph in compressed sparse row format.
+///
+/// The hash of a CSR only depends on its destinations and offsets, so an
+/// owned CSR and its memory-mapped copy have the same hash.
+#[derive(Epserde, Debug, Clone, Hash)]
 pub struct CSR<Destinations, Offsets> {
     pub(crate) destinations: Destinations,
     pub(crate) offsets: Offsets,
 }
 
+/// A CSR owning its destinations and offsets.
+pub type VecCSR<Node, Offset = usize> = CSR<Vec<Node>, Vec<Offset>>;
+
 impl<Destinations, Offsets> CSR<Destinations, Offsets> {
     #[inline(always)]
     pub unsafe fn new(destinations: Destinations, offsets: Offsets) -> Self {
@@ -20,6 +27,46 @@ impl<Destinations, Offsets> CSR<Destinations, Offsets> {
     }
 }
 
+impl<Destinations, Offsets> CSR<Destinations, Offsets>
+where
+    Self: Successors,
+    usize: To<<Self as Graph>::Node>,
+{
+    /// Returns the transposed graph, where the successors of each node are
+    /// its predecessors in this graph, sorted.
+    ///
+    /// The predecessors are needed to visit a directed graph backwards, e.g.
+    /// to compute the in-balls of its nodes.
+    pub fn transpose(&self) -> VecCSR<<Self as Graph>::Node> {
+        let number_of_nodes = self.number_of_nodes();
+
+        // We count the predecessors of each node, and turn the counts into offsets.
+        let mut offsets = vec![0_usize; number_of_nodes + 1];
+        for src in self.nodes() {
+            for dst in self.successors(src) {
+                let dst: usize = dst.to();
+                offsets[dst + 1] += 1;
+            }
+        }
+        for node in 0..number_of_nodes {
+            offsets[node + 1] += offsets[node];
+        }
+
+        // As the sources are visited in order, the predecessors are sorted.
+        let mut positions = offsets[..number_of_nodes].to_vec();
+        let mut destinations = vec![<Self as Graph>::Node::default(); self.number_of_edges()];
+        for src in self.nodes() {
+            for dst in self.successors(src) {
+                let dst: usize = dst.to();
+                destinations[positions[dst]] = src;
+                positions[dst] += 1;
+            }
+        }
+
+        unsafe { CSR::new(destinations, offsets) }
+    }
+}
+
 impl<Destinations: SequenceLen, Offsets: SequenceLen> Graph for CSR<Destinations, Offsets>
 where
     usize: To<Destinations::Item>,
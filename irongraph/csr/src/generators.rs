@@ -0,0 +1,483 @@
+//! Module providing random and deterministic graph generators.
+//!
+//! Every generator builds an undirected graph storing both directions of each
+//! edge, without self-loops or duplicated edges, except for R-MAT which builds
+//! a directed graph and the uniform multigraph which builds a directed graph
+//! keeping self-loops and duplicated edges. The random generators are
+//! deterministic given their seed, on every platform, as they draw from
+//! Xoshiro256++.
+use crate::csr::VecCSR;
+use crate::prelude::CSRBuilder;
+use irontraits::{PositiveInteger, To};
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+use std::collections::HashSet;
+
+/// Returns the CSR of the undirected graph with the provided edges, adding
+/// the reverse edges and removing self-loops and duplicates.
+fn undirected<N, O>(number_of_nodes: usize, edges: Vec<(usize, usize)>) -> VecCSR<N, O>
+where
+    N: PositiveInteger,
+    O: PositiveInteger,
+    usize: To<N>,
+{
+    let mut edges = edges
+        .into_iter()
+        .filter(|(src, dst)| src != dst)
+        .flat_map(|(src, dst)| [(src, dst), (dst, src)])
+        .collect::<Vec<_>>();
+    directed(number_of_nodes, &mut edges)
+}
+
+/// Returns the CSR of the directed graph with the provided edges, removing duplicates.
+fn directed<N, O>(number_of_nodes: usize, edges: &mut Vec<(usize, usize)>) -> VecCSR<N, O>
+where
+    N: PositiveInteger,
+    O: PositiveInteger,
+    usize: To<N>,
+{
+    edges.sort_unstable();
+    edges.dedup();
+
+    CSRBuilder::default()
+        .number_of_edges(edges.len())
+        .number_of_nodes(number_of_nodes)
+        .sequential()
+        .sorted()
+        .build(edges.iter().map(|&(src, dst)| (src.to(), dst.to())))
+}
+
+/// Returns the pair `(i, j)` with `i < j` of the provided index, where the
+/// pairs are enumerated as `(0, 1), (0, 2), (1, 2), (0, 3), ...`.
+#[inline(always)]
+fn pair_from_index(index: u64) -> (usize, usize) {
+    let mut j = ((1.0 + (1.0 + 8.0 * index as f64).sqrt()) / 2.0) as u64;
+    // Fixes the rounding errors of the square root for large indices.
+    while j * (j - 1) / 2 > index {
+        j -= 1;
+    }
+    while (j + 1) * j / 2 <= index {
+        j += 1;
+    }
+    ((index - j * (j - 1) / 2) as usize, j as usize)
+}
+
+/// Calls the closure on every index in `0..count`, each one independently
+/// with the provided probability, skipping the others geometrically.
+fn for_each_sampled<R: Rng>(
+    count: u64,
+    probability: f64,
+    rng: &mut R,
+    mut callback: impl FnMut(u64),
+) {
+    if probability <= 0.0 {
+        return;
+    }
+    if probability >= 1.0 {
+        (0..count).for_each(callback);
+        return;
+    }
+    let log_failure = (1.0 - probability).ln();
+    let mut index = 0_u64;
+    loop {
+        let skip = ((1.0 - rng.gen::<f64>()).ln() / log_failure).floor();
+        if skip >= (count - index) as f64 {
+            return;
+        }
+        index += skip as u64;
+        callback(index);
+        index += 1;
+    }
+}
+
+/// Returns an Erdős–Rényi graph with exactly the provided number of edges,
+/// chosen uniformly among all the pairs of nodes.
+///
+/// # Arguments
+/// * `number_of_nodes`: usize - The number of nodes.
+/// * `number_of_edges`: usize - The number of undirected edges.
+/// * `seed`: u64 - The seed of the generator.
+///
+/// # Panics
+/// If there are fewer pairs of nodes than the requested edges.
+pub fn erdos_renyi_gnm<N, O>(
+    number_of_nodes: usize,
+    number_of_edges: usize,
+    seed: u64,
+) -> VecCSR<N, O>
+where
+    N: PositiveInteger,
+    O: PositiveInteger,
+    usize: To<N>,
+{
+    let number_of_pairs = (number_of_nodes as u64 * number_of_nodes.saturating_sub(1) as u64) / 2;
+    assert!(
+        number_of_edges as u64 <= number_of_pairs,
+        "The graph cannot have more edges than pairs of nodes."
+    );
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+
+    // Floyd's algorithm, sampling distinct pairs with a single draw each.
+    let mut sampled = HashSet::with_capacity(number_of_edges);
+    for upper in (number_of_pairs - number_of_edges as u64)..number_of_pairs {
+        let candidate = rng.gen_range(0..=upper);
+        if !sampled.insert(candidate) {
+            sampled.insert(upper);
+        }
+    }
+    undirected(
+        number_of_nodes,
+        sampled.into_iter().map(pair_from_index).collect(),
+    )
+}
+
+/// Returns an Erdős–Rényi graph where each pair of nodes is connected
+/// independently with the provided probability.
+///
+/// # Arguments
+/// * `number_of_nodes`: usize - The number of nodes.
+/// * `probability`: f64 - The probability of each edge.
+/// * `seed`: u64 - The seed of the generator.
+pub fn erdos_renyi_gnp<N, O>(number_of_nodes: usize, probability: f64, seed: u64) -> VecCSR<N, O>
+where
+    N: PositiveInteger,
+    O: PositiveInteger,
+    usize: To<N>,
+{
+    stochastic_block_model(&[number_of_nodes], &[probability], seed)
+}
+
+/// Returns a Barabási–Albert graph, where each new node is connected to the
+/// provided number of existing nodes chosen with probability proportional
+/// to their degree.
+///
+/// The first new node is connected to all the `edges_per_node` initial nodes.
+///
+/// # Arguments
+/// * `number_of_nodes`: usize - The number of nodes.
+/// * `edges_per_node`: usize - The number of edges of each new node.
+/// * `seed`: u64 - The seed of the generator.
+///
+/// # Panics
+/// If the number of edges per node is zero or not smaller than the number of nodes.
+pub fn barabasi_albert<N, O>(
+    number_of_nodes: usize,
+    edges_per_node: usize,
+    seed: u64,
+) -> VecCSR<N, O>
+where
+    N: PositiveInteger,
+    O: PositiveInteger,
+    usize: To<N>,
+{
+    assert!(
+        edges_per_node > 0 && edges_per_node < number_of_nodes,
+        "The edges per node must be positive and smaller than the number of nodes."
+    );
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+    let mut edges = Vec::with_capacity((number_of_nodes - edges_per_node) * edges_per_node);
+    // Each node appears once for each of its edges, so that sampling
+    // uniformly from it is sampling proportionally to the degree.
+    let mut endpoints = Vec::with_capacity(2 * edges.capacity());
+    let mut targets: Vec<usize> = (0..edges_per_node).collect();
+
+    for src in edges_per_node..number_of_nodes {
+        for &dst in targets.iter() {
+            edges.push((src, dst));
+            endpoints.push(src);
+            endpoints.push(dst);
+        }
+        targets.clear();
+        while targets.len() < edges_per_node {
+            let dst = endpoints[rng.gen_range(0..endpoints.len())];
+            if !targets.contains(&dst) {
+                targets.push(dst);
+            }
+        }
+    }
+    undirected(number_of_nodes, edges)
+}
+
+/// Returns a Watts–Strogatz small-world graph, a ring lattice whose edges
+/// are rewired to a uniformly random node with the provided probability.
+///
+/// # Arguments
+/// * `number_of_nodes`: usize - The number of nodes.
+/// * `neighbours`: usize - The even number of neighbours of each node in the lattice.
+/// * `rewiring_probability`: f64 - The probability of rewiring each edge.
+/// * `seed`: u64 - The seed of the generator.
+///
+/// # Panics
+/// If the number of neighbours is odd or not smaller than the number of nodes.
+pub fn watts_strogatz<N, O>(
+    number_of_nodes: usize,
+    neighbours: usize,
+    rewiring_probability: f64,
+    seed: u64,
+) -> VecCSR<N, O>
+where
+    N: PositiveInteger,
+    O: PositiveInteger,
+    usize: To<N>,
+{
+    assert!(
+        neighbours.is_multiple_of(2) && neighbours < number_of_nodes,
+        "The neighbours must be even and smaller than the number of nodes."
+    );
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+    let normalize = |src: usize, dst: usize| (src.min(dst), src.max(dst));
+    let mut edges: HashSet<(usize, usize)> = (0..number_of_nodes)
+        .flat_map(|src| {
+            (1..=neighbours / 2).map(move |offset| normalize(src, (src + offset) % number_of_nodes))
+        })
+        .collect();
+
+    // The lattice edges are rewired in a fixed order, for determinism.
+    for offset in 1..=neighbours / 2 {
+        for src in 0..number_of_nodes {
+            if rng.gen::<f64>() >= rewiring_probability {
+                continue;
+            }
+            let edge = normalize(src, (src + offset) % number_of_nodes);
+            let dst = rng.gen_range(0..number_of_nodes);
+            let rewired = normalize(src, dst);
+            if dst != src && edges.contains(&edge) && !edges.contains(&rewired) {
+                edges.remove(&edge);
+                edges.insert(rewired);
+            }
+        }
+    }
+    undirected(number_of_nodes, edges.into_iter().collect())
+}
+
+/// Returns a graph from the stochastic block model, where each pair of
+/// nodes is connected independently with a probability depending only on
+/// the blocks of the two nodes.
+///
+/// The nodes of each block are contiguous, following the order of the blocks.
+///
+/// # Arguments
+/// * `block_sizes`: &[usize] - The number of nodes of each block.
+/// * `probabilities`: &[f64] - The symmetric row-major matrix of the edge probabilities between blocks.
+/// * `seed`: u64 - The seed of the generator.
+///
+/// # Panics
+/// If the probabilities are not a square matrix with a row for each block.
+pub fn stochastic_block_model<N, O>(
+    block_sizes: &[usize],
+    probabilities: &[f64],
+    seed: u64,
+) -> VecCSR<N, O>
+where
+    N: PositiveInteger,
+    O: PositiveInteger,
+    usize: To<N>,
+{
+    let number_of_blocks = block_sizes.len();
+    assert_eq!(
+        probabilities.len(),
+        number_of_blocks * number_of_blocks,
+        "The probabilities must be a square matrix with a row for each block."
+    );
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+    let block_offsets: Vec<usize> = block_sizes
+        .iter()
+        .scan(0, |offset, &size| {
+            *offset += size;
+            Some(*offset - size)
+        })
+        .collect();
+
+    let mut edges = Vec::new();
+    for left in 0..number_of_blocks {
+        let (left_offset, left_size) = (block_offsets[left], block_sizes[left]);
+        let pairs = (left_size as u64 * left_size.saturating_sub(1) as u64) / 2;
+        for_each_sampled(
+            pairs,
+            probabilities[left * number_of_blocks + left],
+            &mut rng,
+            |index| {
+                let (src, dst) = pair_from_index(index);
+                edges.push((left_offset + src, left_offset + dst));
+            },
+        );
+        for right in left + 1..number_of_blocks {
+            let (right_offset, right_size) = (block_offsets[right], block_sizes[right]);
+            let pairs = left_size as u64 * right_size as u64;
+            for_each_sampled(
+                pairs,
+                probabilities[left * number_of_blocks + right],
+                &mut rng,
+                |index| {
+                    edges.push((
+                        left_offset + (index / right_size as u64) as usize,
+                        right_offset + (index % right_size as u64) as usize,
+                    ));
+                },
+            );
+        }
+    }
+    undirected(block_sizes.iter().sum(), edges)
+}
+
+/// Returns a directed multigraph whose edges have both endpoints chosen
+/// uniformly at random, keeping self-loops and duplicated edges.
+///
+/// # Arguments
+/// * `number_of_nodes`: usize - The number of nodes.
+/// * `number_of_edges`: usize - The number of directed edges, duplicates included.
+/// * `seed`: u64 - The seed of the generator.
+///
+/// # Panics
+/// If there are edges but no nodes.
+pub fn uniform_multigraph<N, O>(
+    number_of_nodes: usize,
+    number_of_edges: usize,
+    seed: u64,
+) -> VecCSR<N, O>
+where
+    N: PositiveInteger,
+    O: PositiveInteger,
+    usize: To<N>,
+{
+    assert!(
+        number_of_nodes > 0 || number_of_edges == 0,
+        "The edges of the graph need at least one node."
+    );
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+    let mut edges = (0..number_of_edges)
+        .map(|_| {
+            (
+                rng.gen_range(0..number_of_nodes),
+                rng.gen_range(0..number_of_nodes),
+            )
+        })
+        .collect::<Vec<_>>();
+    edges.sort_unstable();
+
+    CSRBuilder::default()
+        .number_of_edges(edges.len())
+        .number_of_nodes(number_of_nodes)
+        .sequential()
+        .sorted()
+        .build(edges.iter().map(|&(src, dst)| (src.to(), dst.to())))
+}
+
+/// Returns a directed R-MAT graph, the stochastic Kronecker graph with a
+/// 2×2 initiator, placing each edge by recursively choosing one of the four
+/// quadrants of the adjacency matrix.
+///
+/// Duplicated edges are removed, so the graph may have fewer edges than requested.
+///
+/// # Arguments
+/// * `scale`: u32 - The logarithm in base 2 of the number of nodes.
+/// * `number_of_edges`: usize - The number of edges to sample.
+/// * `probabilities`: [f64; 3] - The probabilities of the top-left, top-right and bottom-left quadrants.
+/// * `seed`: u64 - The seed of the generator.
+///
+/// # Panics
+/// If the probabilities are negative or sum to more than one.
+pub fn rmat<N, O>(
+    scale: u32,
+    number_of_edges: usize,
+    probabilities: [f64; 3],
+    seed: u64,
+) -> VecCSR<N, O>
+where
+    N: PositiveInteger,
+    O: PositiveInteger,
+    usize: To<N>,
+{
+    let [a, b, c] = probabilities;
+    assert!(
+        a >= 0.0 && b >= 0.0 && c >= 0.0 && a + b + c <= 1.0,
+        "The quadrant probabilities must be non-negative and sum to at most 1."
+    );
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+    let mut edges = (0..number_of_edges)
+        .map(|_| {
+            let (mut src, mut dst) = (0, 0);
+            for _ in 0..scale {
+                let quadrant = rng.gen::<f64>();
+                src <<= 1;
+                dst <<= 1;
+                if quadrant >= a + b + c {
+                    src |= 1;
+                    dst |= 1;
+                } else if quadrant >= a + b {
+                    src |= 1;
+                } else if quadrant >= a {
+                    dst |= 1;
+                }
+            }
+            (src, dst)
+        })
+        .collect();
+    directed(1 << scale, &mut edges)
+}
+
+/// Returns a grid graph, where each node is connected to the nodes above,
+/// below, on the left and on the right of it.
+///
+/// The node in row `i` and column `j` is the node `i * columns + j`.
+pub fn grid<N, O>(rows: usize, columns: usize) -> VecCSR<N, O>
+where
+    N: PositiveInteger,
+    O: PositiveInteger,
+    usize: To<N>,
+{
+    let mut edges = Vec::with_capacity(2 * rows * columns);
+    for row in 0..rows {
+        for column in 0..columns {
+            let node = row * columns + column;
+            if column + 1 < columns {
+                edges.push((node, node + 1));
+            }
+            if row + 1 < rows {
+                edges.push((node, node + columns));
+            }
+        }
+    }
+    undirected(rows * columns, edges)
+}
+
+/// Returns a star graph, where the node 0 is connected to all the others.
+pub fn star<N, O>(number_of_nodes: usize) -> VecCSR<N, O>
+where
+    N: PositiveInteger,
+    O: PositiveInteger,
+    usize: To<N>,
+{
+    undirected(
+        number_of_nodes,
+        (1..number_of_nodes).map(|leaf| (0, leaf)).collect(),
+    )
+}
+
+/// Returns a complete graph, where every pair of nodes is connected.
+pub fn clique<N, O>(number_of_nodes: usize) -> VecCSR<N, O>
+where
+    N: PositiveInteger,
+    O: PositiveInteger,
+    usize: To<N>,
+{
+    undirected(
+        number_of_nodes,
+        (0..number_of_nodes)
+            .flat_map(|src| (src + 1..number_of_nodes).map(move |dst| (src, dst)))
+            .collect(),
+    )
+}
+
+/// Returns a path graph, where each node is connected to the next one.
+pub fn path<N, O>(number_of_nodes: usize) -> VecCSR<N, O>
+where
+    N: PositiveInteger,
+    O: PositiveInteger,
+    usize: To<N>,
+{
+    undirected(
+        number_of_nodes,
+        (1..number_of_nodes).map(|dst| (dst - 1, dst)).collect(),
+    )
+}
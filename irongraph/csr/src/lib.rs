@@ -6,7 +6,10 @@ compile_error!("Rayon requires std");
 #[cfg_attr(not(feature = "std"), no_std)]
 pub mod builders;
 pub mod csr;
+#[cfg(feature = "generators")]
+pub mod generators;
 pub mod iter;
+pub mod weighted;
 
 #[cfg(feature = "rayon")]
 pub mod par_iter;
@@ -14,4 +17,5 @@ pub mod par_iter;
 pub mod prelude {
     pub use super::builders::*;
     pub use super::csr::*;
+    pub use super::weighted::*;
 }
@@ -0,0 +1,137 @@
+use crate::csr::CSR;
+use core::borrow::Borrow;
+use epserde::Epserde;
+use graph::{Graph, Successors, WeightedSuccessors};
+use irontraits::{SequenceRandomAccess, To};
+
+/// A CSR with a weight associated to each edge, stored in the same order as
+/// the destinations.
+#[derive(Epserde, Debug, Clone)]
+pub struct WeightedCSR<Destinations, Offsets, Weights> {
+    pub(crate) csr: CSR<Destinations, Offsets>,
+    pub(crate) weights: Weights,
+}
+
+/// A weighted CSR owning its destinations, offsets and weights.
+pub type VecWeightedCSR<Node, Weight, Offset = usize> =
+    WeightedCSR<Vec<Node>, Vec<Offset>, Vec<Weight>>;
+
+impl<Destinations, Offsets, Weights> WeightedCSR<Destinations, Offsets, Weights> {
+    /// # Safety
+    /// The weights must have the same length as the destinations of the CSR.
+    #[inline(always)]
+    pub unsafe fn new(csr: CSR<Destinations, Offsets>, weights: Weights) -> Self {
+        Self { csr, weights }
+    }
+
+    #[inline(always)]
+    pub fn csr(&self) -> &CSR<Destinations, Offsets> {
+        &self.csr
+    }
+
+    #[inline(always)]
+    pub fn weights(&self) -> &Weights {
+        &self.weights
+    }
+}
+
+impl<Destinations, Offsets, Weights> Graph for WeightedCSR<Destinations, Offsets, Weights>
+where
+    CSR<Destinations, Offsets>: Graph,
+{
+    type Node = <CSR<Destinations, Offsets> as Graph>::Node;
+
+    #[inline(always)]
+    fn directed(&self) -> bool {
+        self.csr.directed()
+    }
+
+    #[inline(always)]
+    fn number_of_nodes(&self) -> usize {
+        self.csr.number_of_nodes()
+    }
+
+    #[inline(always)]
+    fn number_of_edges(&self) -> usize {
+        self.csr.number_of_edges()
+    }
+
+    type Nodes = <CSR<Destinations, Offsets> as Graph>::Nodes;
+    #[inline(always)]
+    fn nodes(&self) -> Self::Nodes {
+        self.csr.nodes()
+    }
+}
+
+impl<Destinations, Offsets, Weights> Successors for WeightedCSR<Destinations, Offsets, Weights>
+where
+    CSR<Destinations, Offsets>: Successors,
+{
+    type Successors<'a> = <CSR<Destinations, Offsets> as Successors>::Successors<'a>
+    where
+        Self: 'a;
+
+    #[inline(always)]
+    fn successors<N: Borrow<Self::Node>>(&self, node: N) -> Self::Successors<'_> {
+        self.csr.successors(node)
+    }
+
+    #[inline(always)]
+    fn has_successor<S: Borrow<Self::Node>, D: Borrow<Self::Node>>(&self, src: S, dst: D) -> bool {
+        self.csr.has_successor(src, dst)
+    }
+}
+
+impl<Destinations, Offsets: SequenceRandomAccess, Weight: Copy> WeightedSuccessors
+    for WeightedCSR<Destinations, Offsets, Vec<Weight>>
+where
+    CSR<Destinations, Offsets>: Successors,
+    Offsets::Item: To<usize>,
+{
+    type Weight = Weight;
+
+    type WeightedSuccessors<'a> = core::iter::Zip<
+        <<CSR<Destinations, Offsets> as Successors>::Successors<'a> as IntoIterator>::IntoIter,
+        core::iter::Copied<core::slice::Iter<'a, Weight>>,
+    >
+    where
+        Self: 'a;
+
+    #[inline(always)]
+    fn weighted_successors<N: Borrow<Self::Node>>(&self, node: N) -> Self::WeightedSuccessors<'_> {
+        let index: usize = (*node.borrow()).to();
+        let start = self.csr.offsets.get(index).to();
+        let end = self.csr.offsets.get(index + 1).to();
+        self.csr
+            .successors(node)
+            .into_iter()
+            .zip(self.weights[start..end].iter().copied())
+    }
+}
+
+impl<Destinations, Offsets: SequenceRandomAccess, Weight: Copy> WeightedSuccessors
+    for WeightedCSR<Destinations, Offsets, &[Weight]>
+where
+    CSR<Destinations, Offsets>: Successors,
+    Offsets::Item: To<usize>,
+{
+    type Weight = Weight;
+
+    type WeightedSuccessors<'a> = core::iter::Zip<
+        <<CSR<Destinations, Offsets> as Successors>::Successors<'a> as IntoIterator>::IntoIter,
+        core::iter::Copied<core::slice::Iter<'a, Weight>>,
+    >
+    where
+        Self: 'a;
+
+    #[inline(always)]
+    fn weighted_successors<N: Borrow<Self::Node>>(&self, node: N) -> Self::WeightedSuccessors<'_> {
+        let index: usize = (*node.borrow()).to();
+        let start = self.csr.offsets.get(index).to();
+        let end = self.csr.offsets.get(index + 1).to();
+        self.csr
+            .successors(node)
+            .into_iter()
+            .zip(self.weights[start..end].iter().copied())
+    }
+}
@@ -2,6 +2,14 @@ use csr::prelude::*;
 use epserde::{deser::Deserialize, ser::Serialize};
 use graph::*;
 use irontraits::*;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+fn hash_of<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
 
 #[test]
 fn test_build_csr() {
@@ -47,3 +55,86 @@ fn test_build_csr() {
     assert_eq!(csr2.successors(3).into_iter().collect::<Vec<_>>(), vec![4]);
     assert_eq!(csr2.successors(4).into_iter().collect::<Vec<_>>(), vec![]);
 }
+
+#[test]
+fn test_csr_hash() {
+    let edges: Vec<(usize, usize)> = vec![(0, 1), (0, 2), (1, 2), (1, 3), (2, 3), (3, 4)];
+    let number_of_edges = edges.len();
+
+    let csr: CSR<Vec<usize>, Vec<usize>> = CSRBuilder::default()
+        .number_of_edges(number_of_edges)
+        .number_of_nodes(5)
+        .sorted()
+        .build(edges.iter().copied());
+
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join("csr_hash.csr");
+    csr.store(&path).unwrap();
+    let csr2 = CSR::<Vec<usize>, Vec<usize>>::mmap(&path, Default::default()).unwrap();
+
+    // The memory-mapped copy hashes as the owned one, while another graph does not.
+    assert_eq!(hash_of(&*csr2), hash_of(&csr));
+    let other: CSR<Vec<usize>, Vec<usize>> = CSRBuilder::default()
+        .number_of_edges(number_of_edges - 1)
+        .number_of_nodes(5)
+        .sorted()
+        .build(edges[1..].iter().copied());
+    assert_ne!(hash_of(&other), hash_of(&csr));
+}
+
+#[test]
+fn test_build_weighted_csr() {
+    let edges: Vec<(usize, usize, f32)> = vec![(0, 1, 0.5), (0, 2, 1.5), (1, 2, 2.0), (2, 0, 3.0)];
+    let number_of_edges = edges.len();
+
+    let csr: WeightedCSR<Vec<usize>, Vec<usize>, Vec<f32>> = CSRBuilder::default()
+        .number_of_edges(number_of_edges)
+        .number_of_nodes(4)
+        .sorted()
+        .build_weighted(edges);
+
+    assert_eq!(csr.number_of_nodes(), 4);
+    assert_eq!(csr.number_of_edges(), number_of_edges);
+    assert!(csr.has_successor(1, 2));
+    assert_eq!(
+        csr.weighted_successors(0).into_iter().collect::<Vec<_>>(),
+        vec![(1, 0.5), (2, 1.5)]
+    );
+    assert_eq!(
+        csr.weighted_successors(3).into_iter().collect::<Vec<_>>(),
+        vec![]
+    );
+
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join("weighted.csr");
+    csr.store(&path).unwrap();
+
+    let csr2 =
+        WeightedCSR::<Vec<usize>, Vec<usize>, Vec<f32>>::mmap(&path, Default::default()).unwrap();
+
+    assert_eq!(csr2.number_of_edges(), number_of_edges);
+    assert_eq!(
+        csr2.weighted_successors(2).into_iter().collect::<Vec<_>>(),
+        vec![(0, 3.0)]
+    );
+}
+
+#[test]
+fn test_transpose() {
+    let edges: Vec<(usize, usize)> = vec![(0, 1), (0, 2), (1, 2), (1, 3), (2, 3), (3, 0), (3, 4)];
+
+    let csr: CSR<Vec<usize>, Vec<usize>> = CSRBuilder::default()
+        .number_of_edges(edges.len())
+        .number_of_nodes(5)
+        .sorted()
+        .build(edges.iter().copied());
+    let transposed = csr.transpose();
+
+    assert_eq!(transposed.number_of_nodes(), 5);
+    assert_eq!(transposed.number_of_edges(), edges.len());
+    assert_eq!(transposed.successors(0).into_iter().collect::<Vec<_>>(), vec![3]);
+    assert_eq!(transposed.successors(2).into_iter().collect::<Vec<_>>(), vec![0, 1]);
+    assert_eq!(transposed.successors(3).into_iter().collect::<Vec<_>>(), vec![1, 2]);
+    assert!(transposed.has_successor(4, 3));
+    assert_eq!(hash_of(&transposed.transpose()), hash_of(&csr));
+}
@@ -0,0 +1,88 @@
+use csr::generators::*;
+use csr::prelude::*;
+use graph::*;
+
+/// Checks that the graph is undirected, without self-loops and duplicated edges.
+fn assert_simple_undirected(graph: &VecCSR<usize>) {
+    for src in 0..graph.number_of_nodes() {
+        let successors = graph.successors(src).into_iter().collect::<Vec<_>>();
+        assert!(successors.windows(2).all(|pair| pair[0] < pair[1]));
+        for dst in successors {
+            assert_ne!(src, dst);
+            assert!(graph.has_successor(dst, src));
+        }
+    }
+}
+
+fn degree(graph: &VecCSR<usize>, node: usize) -> usize {
+    graph.successors(node).into_iter().count()
+}
+
+#[test]
+fn test_deterministic_generators() {
+    let path: VecCSR<usize> = path(5);
+    assert_eq!(path.number_of_edges(), 8);
+    assert_simple_undirected(&path);
+
+    let star: VecCSR<usize> = star(6);
+    assert_eq!(degree(&star, 0), 5);
+    assert_eq!(star.number_of_edges(), 10);
+
+    let clique: VecCSR<usize> = clique(6);
+    assert_eq!(clique.number_of_edges(), 30);
+    assert_simple_undirected(&clique);
+
+    let grid: VecCSR<usize> = grid(3, 4);
+    assert_eq!(grid.number_of_nodes(), 12);
+    assert_eq!(grid.number_of_edges(), 2 * (3 * 3 + 2 * 4));
+    assert_eq!(degree(&grid, 0), 2);
+    assert_eq!(degree(&grid, 5), 4);
+    assert_simple_undirected(&grid);
+}
+
+#[test]
+fn test_random_generators() {
+    let gnm: VecCSR<usize> = erdos_renyi_gnm(100, 300, 42);
+    assert_eq!(gnm.number_of_edges(), 600);
+    assert_simple_undirected(&gnm);
+    let complete: VecCSR<usize> = erdos_renyi_gnm(10, 45, 42);
+    assert_eq!(complete.number_of_edges(), 90);
+
+    let gnp: VecCSR<usize> = erdos_renyi_gnp(200, 0.05, 42);
+    let expected = 0.05 * 200.0 * 199.0;
+    assert!((gnp.number_of_edges() as f64 - expected).abs() < 0.2 * expected);
+    assert_simple_undirected(&gnp);
+
+    let barabasi_albert: VecCSR<usize> = barabasi_albert(100, 3, 42);
+    assert_eq!(barabasi_albert.number_of_edges(), 2 * 3 * 97);
+    assert_simple_undirected(&barabasi_albert);
+
+    let lattice: VecCSR<usize> = watts_strogatz(50, 4, 0.0, 42);
+    assert!((0..50).all(|node| degree(&lattice, node) == 4));
+    let small_world: VecCSR<usize> = watts_strogatz(50, 4, 0.3, 42);
+    assert_eq!(small_world.number_of_edges(), 200);
+    assert_simple_undirected(&small_world);
+
+    let blocks: VecCSR<usize> = stochastic_block_model(&[20, 30], &[1.0, 0.0, 0.0, 1.0], 42);
+    assert_eq!(blocks.number_of_edges(), 20 * 19 + 30 * 29);
+    assert!(!blocks.has_successor(0, 20));
+    assert_simple_undirected(&blocks);
+
+    let rmat: VecCSR<u32, u64> = rmat(10, 5000, [0.57, 0.19, 0.19], 42);
+    assert_eq!(rmat.number_of_nodes(), 1024);
+    assert!(rmat.number_of_edges() <= 5000);
+
+    let multigraph: VecCSR<usize> = uniform_multigraph(10, 500, 42);
+    assert_eq!(multigraph.number_of_edges(), 500);
+    // With 100 pairs of nodes, some edges are duplicated or self-loops.
+    assert!((0..10).any(|node| {
+        let successors = multigraph.successors(node).into_iter().collect::<Vec<_>>();
+        successors.contains(&node) || successors.windows(2).any(|pair| pair[0] == pair[1])
+    }));
+
+    // The same seed always produces the same graph.
+    let again: VecCSR<usize> = erdos_renyi_gnp(200, 0.05, 42);
+    for node in 0..gnp.number_of_nodes() {
+        assert!(gnp.successors(node).into_iter().eq(again.successors(node)));
+    }
+}
@@ -30,3 +30,15 @@ pub trait Successors: Graph {
     // returns false if src or dst don't exist
     fn has_successor<S: Borrow<Self::Node>, D: Borrow<Self::Node>>(&self, src: S, dst: D) -> bool;
 }
+
+pub trait WeightedSuccessors: Successors {
+    type Weight: Copy;
+
+    type WeightedSuccessors<'a>: IntoIterator<Item = (Self::Node, Self::Weight)> + 'a
+    where
+        Self: 'a;
+
+    // panic if node doesn't exist
+    // the successors are returned in the same order as in `successors`
+    fn weighted_successors<N: Borrow<Self::Node>>(&self, node: N) -> Self::WeightedSuccessors<'_>;
+}
@@ -0,0 +1,323 @@
+//! Module providing the sketches of the in-balls of the nodes of directed
+//! graphs, alongside the sketches of their out-balls.
+//!
+//! A [`HyperSketching`] only visits the successors of the nodes, so its
+//! balls are out-balls. The in-balls are the out-balls of the transposed
+//! graph, e.g. as returned by `CSR::transpose`, so that the edge features can
+//! compare the out-ball of the source with the in-ball of the destination,
+//! which is what matters to predict directed edges.
+//!
+//! Only the biased features are provided: the bias-aware features of a
+//! [`HyperSketching`] visit the balls of the source and of the destination
+//! on its graph, masking the nodes reached through the edge being predicted.
+//! The out-ball of the source and the in-ball of the destination are balls of
+//! two different graphs, the graph and its transpose, so that no visit of a
+//! single graph can compute their masks.
+use crate::layout::FeatureLayout;
+use crate::sketches::SetSketch;
+use crate::{HyperSketching, HyperSketchingData};
+use algorithms::features::EdgeFeatures;
+use anyhow::{bail, ensure, Result};
+use graph::{Graph, Successors};
+use hyperloglog_rs::prelude::*;
+use irontraits::{IntoIndexedParallelIterator, Sequence, SequenceAllocable, To};
+
+/// The direction of the balls of a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ball {
+    /// The nodes reachable from the node.
+    Out,
+    /// The nodes from which the node is reachable.
+    In,
+}
+
+/// The sketches of the out-balls and of the in-balls of the nodes of a
+/// directed graph, each of them optional.
+pub struct DirectedHyperSketching<G, T, Counters, S = HyperLogLog<Precision12, 6>>
+where
+    S: SetSketch,
+    Counters: Sequence<Item = S>,
+{
+    /// The sketches of the out-balls, fitted on the graph.
+    out_balls: Option<HyperSketching<G, HyperSketchingData<Counters>, Counters, S>>,
+    /// The sketches of the in-balls, fitted on the transposed graph.
+    in_balls: Option<HyperSketching<T, HyperSketchingData<Counters>, Counters, S>>,
+}
+
+impl<G, T, Counters, S> DirectedHyperSketching<G, T, Counters, S>
+where
+    G: Graph,
+    T: Graph<Node = G::Node>,
+    Counters: SequenceAllocable<Item = S>,
+    S: SetSketch,
+{
+    /// Creates a new model of the out-balls, of the in-balls or of both.
+    ///
+    /// # Arguments
+    /// * `number_of_hops`: usize - The number of hops to use for the Sketching.
+    /// * `normalize`: bool - Whether to normalize the Sketching cardinalities.
+    /// * `graph`: Option<G> - The graph whose out-balls are sketched, if any.
+    /// * `transposed`: Option<T> - The transposed graph, whose out-balls are the in-balls of the graph, if any.
+    pub fn new(
+        number_of_hops: usize,
+        normalize: bool,
+        graph: Option<G>,
+        transposed: Option<T>,
+    ) -> Result<Self> {
+        Self::from_sketchings(
+            graph
+                .map(|graph| HyperSketching::new(number_of_hops, normalize, graph))
+                .transpose()?,
+            transposed
+                .map(|transposed| HyperSketching::new(number_of_hops, normalize, transposed))
+                .transpose()?,
+        )
+    }
+
+    /// Creates the model from the sketches of the out-balls and of the
+    /// in-balls, e.g. loaded back after being stored separately.
+    ///
+    /// # Arguments
+    /// * `out_balls`: Option<HyperSketching> - The sketches fitted on the graph, if any.
+    /// * `in_balls`: Option<HyperSketching> - The sketches fitted on the transposed graph, if any.
+    pub fn from_sketchings(
+        out_balls: Option<HyperSketching<G, HyperSketchingData<Counters>, Counters, S>>,
+        in_balls: Option<HyperSketching<T, HyperSketchingData<Counters>, Counters, S>>,
+    ) -> Result<Self> {
+        match (&out_balls, &in_balls) {
+            (None, None) => bail!("Either the out-balls or the in-balls must be sketched."),
+            (Some(out_balls), Some(in_balls)) => {
+                ensure!(
+                    out_balls.graph.number_of_nodes() == in_balls.graph.number_of_nodes(),
+                    "The graph has {} nodes, but the transposed graph has {} nodes.",
+                    out_balls.graph.number_of_nodes(),
+                    in_balls.graph.number_of_nodes()
+                );
+                ensure!(
+                    out_balls.number_of_hops() == in_balls.number_of_hops()
+                        && out_balls.retained_hops() == in_balls.retained_hops(),
+                    "The out-balls and the in-balls must be sketched with the same hops."
+                );
+                ensure!(
+                    out_balls.normalize() == in_balls.normalize(),
+                    "The out-balls and the in-balls must be both normalized or both not normalized."
+                );
+            }
+            _ => {}
+        }
+        Ok(Self {
+            out_balls,
+            in_balls,
+        })
+    }
+
+    /// Sets the hops whose counters are retained by the next fit of both
+    /// balls, and drops the current counters.
+    pub fn set_retained_hops(&mut self, retained_hops: &[usize]) -> Result<()> {
+        if let Some(out_balls) = self.out_balls.as_mut() {
+            out_balls.set_retained_hops(retained_hops)?;
+        }
+        if let Some(in_balls) = self.in_balls.as_mut() {
+            in_balls.set_retained_hops(retained_hops)?;
+        }
+        Ok(())
+    }
+}
+
+impl<G, T, Counters, S> DirectedHyperSketching<G, T, Counters, S>
+where
+    G: Graph,
+    T: Graph<Node = G::Node>,
+    Counters: Sequence<Item = S>,
+    S: SetSketch,
+{
+    /// Returns the sketches of the out-balls, if any.
+    #[inline(always)]
+    pub fn out_balls(
+        &self,
+    ) -> Option<&HyperSketching<G, HyperSketchingData<Counters>, Counters, S>> {
+        self.out_balls.as_ref()
+    }
+
+    /// Returns the sketches of the in-balls, if any.
+    #[inline(always)]
+    pub fn in_balls(
+        &self,
+    ) -> Option<&HyperSketching<T, HyperSketchingData<Counters>, Counters, S>> {
+        self.in_balls.as_ref()
+    }
+
+    /// Returns whether the balls of the provided direction are sketched.
+    #[inline(always)]
+    pub fn has_balls(&self, ball: Ball) -> bool {
+        match ball {
+            Ball::Out => self.out_balls.is_some(),
+            Ball::In => self.in_balls.is_some(),
+        }
+    }
+
+    /// Returns the hops whose counters are retained.
+    pub fn retained_hops(&self) -> &[usize] {
+        match (&self.out_balls, &self.in_balls) {
+            (Some(out_balls), _) => out_balls.retained_hops(),
+            (None, Some(in_balls)) => in_balls.retained_hops(),
+            (None, None) => unreachable!("Either the out-balls or the in-balls are sketched."),
+        }
+    }
+
+    /// Returns the layout of the features of each edge, computed on the
+    /// retained hops.
+    #[inline(always)]
+    pub fn feature_layout(&self) -> FeatureLayout {
+        FeatureLayout::new(self.retained_hops())
+    }
+
+    /// Returns whether the cardinalities are normalized.
+    pub fn normalize(&self) -> bool {
+        match (&self.out_balls, &self.in_balls) {
+            (Some(out_balls), _) => out_balls.normalize(),
+            (None, Some(in_balls)) => in_balls.normalize(),
+            (None, None) => unreachable!("Either the out-balls or the in-balls are sketched."),
+        }
+    }
+}
+
+impl<G, T, Counters, S> DirectedHyperSketching<G, T, Counters, S>
+where
+    G: Successors + Send + Sync,
+    <G as Graph>::Nodes: IntoIndexedParallelIterator<Item = G::Node>,
+    T: Successors<Node = G::Node> + Send + Sync,
+    <T as Graph>::Nodes: IntoIndexedParallelIterator<Item = G::Node>,
+    Counters: Send + Sync + SequenceAllocable<Item = S> + AsRef<[S]> + AsMut<[S]>,
+    S: SetSketch,
+{
+    /// Fits the sketches of the out-balls and of the in-balls, when present.
+    pub fn fit(&mut self) {
+        if let Some(out_balls) = self.out_balls.as_mut() {
+            out_balls.fit();
+        }
+        if let Some(in_balls) = self.in_balls.as_mut() {
+            in_balls.fit();
+        }
+    }
+}
+
+impl<G, T, Counters, S> DirectedHyperSketching<G, T, Counters, S>
+where
+    G: Graph,
+    T: Graph<Node = G::Node>,
+    Counters: Sequence<Item = S> + AsRef<[S]>,
+    S: SetSketch,
+{
+    /// Returns the counters of the retained hops of the ball of the node.
+    ///
+    /// # Panics
+    /// If the balls of the provided direction are not sketched.
+    fn counters(&self, node: G::Node, ball: Ball) -> &[S] {
+        let node: usize = node.to();
+        let hops = self.retained_hops().len();
+        let counters = match ball {
+            Ball::Out => self
+                .out_balls
+                .as_ref()
+                .map(|out_balls| &out_balls.data.counters),
+            Ball::In => self
+                .in_balls
+                .as_ref()
+                .map(|in_balls| &in_balls.data.counters),
+        }
+        .unwrap_or_else(|| panic!("The {:?} balls are not sketched.", ball))
+        .as_ref();
+        &counters[node * hops..(node + 1) * hops]
+    }
+
+    /// Writes the biased features of the edge into the zeroed target,
+    /// comparing the provided balls of its source and of its destination.
+    ///
+    /// # Arguments
+    /// * `edge`: (G::Node, G::Node) - The source and destination of the edge.
+    /// * `source`: Ball - The direction of the balls of the source.
+    /// * `destination`: Ball - The direction of the balls of the destination.
+    /// * `target`: &mut [f32] - The zeroed features of the edge.
+    ///
+    /// # Panics
+    /// If the balls of the provided directions are not sketched.
+    pub fn biased_edge_features(
+        &self,
+        (src, dst): (G::Node, G::Node),
+        source: Ball,
+        destination: Ball,
+        target: &mut [f32],
+    ) {
+        S::overlaps_and_differences(
+            self.counters(src, source),
+            self.counters(dst, destination),
+            self.normalize(),
+            target,
+        );
+    }
+
+    /// Returns the extractor of the biased edge features comparing the
+    /// provided balls of the sources and of the destinations, usually the
+    /// out-balls of the sources with the in-balls of the destinations.
+    ///
+    /// # Arguments
+    /// * `source`: Ball - The direction of the balls of the sources.
+    /// * `destination`: Ball - The direction of the balls of the destinations.
+    pub fn biased_features(
+        &self,
+        source: Ball,
+        destination: Ball,
+    ) -> Result<DirectedEdgeFeatures<'_, G, T, Counters, S>> {
+        for ball in [source, destination] {
+            ensure!(
+                self.has_balls(ball),
+                "The {:?} balls are not sketched.",
+                ball
+            );
+        }
+        Ok(DirectedEdgeFeatures {
+            sketching: self,
+            source,
+            destination,
+        })
+    }
+}
+
+/// The biased edge features of a [`DirectedHyperSketching`], comparing the
+/// balls of the sources and of the destinations in the provided directions.
+pub struct DirectedEdgeFeatures<'a, G, T, Counters, S>
+where
+    S: SetSketch,
+    Counters: Sequence<Item = S>,
+{
+    sketching: &'a DirectedHyperSketching<G, T, Counters, S>,
+    source: Ball,
+    destination: Ball,
+}
+
+impl<G, T, Counters, S> EdgeFeatures for DirectedEdgeFeatures<'_, G, T, Counters, S>
+where
+    G: Graph + Sync,
+    T: Graph<Node = G::Node> + Sync,
+    Counters: Sequence<Item = S> + AsRef<[S]> + Sync,
+    S: SetSketch,
+{
+    type Node = G::Node;
+
+    #[inline(always)]
+    fn feature_len(&self) -> usize {
+        self.sketching.feature_layout().len()
+    }
+
+    #[inline(always)]
+    fn feature_names(&self) -> Vec<String> {
+        self.sketching.feature_layout().names()
+    }
+
+    #[inline(always)]
+    fn edge_features(&self, edge: (G::Node, G::Node), target: &mut [f32]) {
+        self.sketching
+            .biased_edge_features(edge, self.source, self.destination, target)
+    }
+}
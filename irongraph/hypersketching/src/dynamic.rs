@@ -0,0 +1,488 @@
+//! Module providing a HyperSketching whose precision and number of bits per
+//! register are chosen at runtime, e.g. by the Python bindings or a CLI.
+//!
+//! The model dispatches over the supported pairs of precision and bits, each
+//! backed by a [`HyperSketching`] with counters owned in a vector or, once
+//! stored, memory-mapped from the file.
+use crate::exact::AccuracyReport;
+use crate::layout::FeatureLayout;
+use crate::statistics::GraphStatistics;
+use crate::{DeserType, HyperSketching, HyperSketchingData};
+use algorithms::features::EdgeFeatures;
+use anyhow::{bail, Result};
+use epserde::deser::{self, Deserialize, DeserializeInner, Flags};
+use graph::{Graph, Successors};
+use hyperloglog_rs::prelude::*;
+use irontraits::IntoIndexedParallelIterator;
+use rayon::prelude::*;
+use std::path::Path;
+
+/// The HyperSketching with counters of the provided precision and bits owned in a vector.
+pub type VecHyperSketching<G, P, const BITS: usize> = HyperSketching<
+    G,
+    HyperSketchingData<Vec<HyperLogLog<P, BITS>>>,
+    Vec<HyperLogLog<P, BITS>>,
+    HyperLogLog<P, BITS>,
+>;
+
+/// The HyperSketching with counters of the provided precision and bits,
+/// whose data is held as provided by the storage.
+pub type StoredHyperSketching<G, D, P, const BITS: usize> = HyperSketching<
+    G,
+    <D as StorageOf<HyperLogLog<P, BITS>>>::Data,
+    Vec<HyperLogLog<P, BITS>>,
+    HyperLogLog<P, BITS>,
+>;
+
+/// The storage of the data of the models whose counters are owned in a vector.
+pub struct Owned;
+
+/// The storage of the data of the models memory-mapped from a file, which
+/// can compute the features but cannot be fitted again.
+pub struct Mapped;
+
+/// The data of a model with the provided counters, as held by the storage.
+pub trait StorageOf<S> {
+    /// The data of the model.
+    type Data: AsRef<HyperSketchingData<Vec<S>>> + Send + Sync;
+}
+
+impl<S: Send + Sync> StorageOf<S> for Owned {
+    type Data = HyperSketchingData<Vec<S>>;
+}
+
+impl<S> StorageOf<S> for Mapped
+where
+    HyperSketchingData<Vec<S>>: DeserializeInner,
+    DeserType<Vec<S>>: AsRef<HyperSketchingData<Vec<S>>> + Send + Sync,
+{
+    type Data = DeserType<Vec<S>>;
+}
+
+/// Calls the provided macro with the arguments followed by the supported
+/// configurations, as the variant, the precision and the number of bits.
+macro_rules! with_configurations {
+    ($callback:ident!($($arguments:tt)*)) => {
+        $callback!(($($arguments)*) [
+            (P8B5, Precision8, 5),
+            (P8B6, Precision8, 6),
+            (P10B5, Precision10, 5),
+            (P10B6, Precision10, 6),
+            (P12B5, Precision12, 5),
+            (P12B6, Precision12, 6),
+            (P14B5, Precision14, 5),
+            (P14B6, Precision14, 6),
+            (P16B5, Precision16, 5),
+            (P16B6, Precision16, 6),
+        ])
+    };
+}
+
+macro_rules! define_dynamic_hyper_sketching {
+    (() [$(($variant:ident, $precision:ty, $bits:literal)),* $(,)?]) => {
+        /// The storage of the data of a [`DynamicHyperSketching`], for every
+        /// supported configuration.
+        pub trait Storage: $(StorageOf<HyperLogLog<$precision, $bits>> +)* Sized {}
+
+        impl<D> Storage for D where D: $(StorageOf<HyperLogLog<$precision, $bits>> +)* Sized {}
+
+        /// A HyperSketching whose precision and number of bits per register
+        /// are chosen at runtime among the supported configurations.
+        pub enum DynamicHyperSketching<G, D: Storage = Owned> {
+            $(
+                #[doc = concat!(
+                    "Counters with `", stringify!($precision), "` and ", stringify!($bits), " bits."
+                )]
+                $variant(StoredHyperSketching<G, D, $precision, $bits>),
+            )*
+        }
+
+        /// The supported pairs of precision and number of bits per register.
+        pub const SUPPORTED_CONFIGURATIONS: &[(usize, usize)] =
+            &[$((<$precision as Precision>::EXPONENT, $bits)),*];
+
+        impl<G: Graph> DynamicHyperSketching<G> {
+            /// Creates a new HyperSketching model with the provided configuration.
+            ///
+            /// # Arguments
+            /// * `precision`: usize - The base-2 logarithm of the number of registers.
+            /// * `bits`: usize - The number of bits of each register of the counters.
+            /// * `number_of_hops`: usize - The number of hops to use for the Sketching.
+            /// * `normalize`: bool - Whether to normalize the Sketching cardinalities.
+            /// * `graph`: Graph - The graph whose edges are to be learned.
+            pub fn new(
+                precision: usize,
+                bits: usize,
+                number_of_hops: usize,
+                normalize: bool,
+                graph: G,
+            ) -> Result<Self> {
+                $(
+                    if (precision, bits) == (<$precision as Precision>::EXPONENT, $bits) {
+                        return Ok(Self::$variant(HyperSketching::new(
+                            number_of_hops,
+                            normalize,
+                            graph,
+                        )?));
+                    }
+                )*
+                unsupported_configuration(precision, bits)
+            }
+        }
+
+        impl<G: Successors> DynamicHyperSketching<G> {
+            /// Loads the data stored by a model, with the configuration it was
+            /// fitted with.
+            ///
+            /// The configuration is found from the type of the counters, whose
+            /// hash epserde records in the header of the stored data and checks
+            /// before reading the rest of it: the configurations whose type hash
+            /// does not match are skipped, while any other error, e.g. of a
+            /// truncated file or of a different graph, is returned.
+            ///
+            /// # Arguments
+            /// * `path`: PP - The path of the stored data.
+            /// * `graph`: Graph - The graph the model was fitted on.
+            /// * `check_graph_hash`: bool - Whether to check the hash of the graph.
+            ///
+            /// # Safety
+            /// As for [`HyperSketching::load`], the data is trusted to be well formed.
+            pub unsafe fn load<PP>(path: PP, graph: G, check_graph_hash: bool) -> Result<Self>
+            where
+                PP: AsRef<Path>,
+            {
+                let path = path.as_ref();
+                $(
+                    let data =
+                        <HyperSketchingData<Vec<HyperLogLog<$precision, $bits>>>>::load_full(path);
+                    match data {
+                        Ok(data) => {
+                            return Ok(Self::$variant(HyperSketching::from_data(
+                                data,
+                                graph,
+                                check_graph_hash,
+                            )?));
+                        }
+                        Err(error) if is_other_configuration(&error) => {}
+                        Err(error) => return Err(loading_error(error, path)),
+                    }
+                )*
+                unsupported_stored_configuration(path)
+            }
+        }
+
+        impl<G: Successors> DynamicHyperSketching<G, Mapped> {
+            /// Memory-maps the data stored by a model, with the configuration
+            /// it was fitted with, found from the header of the stored data as
+            /// by [`DynamicHyperSketching::load`].
+            ///
+            /// # Arguments
+            /// * `path`: PP - The path of the stored data.
+            /// * `graph`: Graph - The graph the model was fitted on.
+            /// * `check_graph_hash`: bool - Whether to check the hash of the graph.
+            ///
+            /// # Safety
+            /// As for [`HyperSketching::mmap`], the data is trusted to be well
+            /// formed and the file not to be modified while it is mapped.
+            pub unsafe fn mmap<PP>(path: PP, graph: G, check_graph_hash: bool) -> Result<Self>
+            where
+                PP: AsRef<Path>,
+            {
+                let path = path.as_ref();
+                $(
+                    let data =
+                        <HyperSketchingData<Vec<HyperLogLog<$precision, $bits>>>>::mmap::<'static>(
+                            path,
+                            Flags::RANDOM_ACCESS,
+                        );
+                    match data {
+                        Ok(data) => {
+                            return Ok(Self::$variant(HyperSketching::from_data(
+                                data,
+                                graph,
+                                check_graph_hash,
+                            )?));
+                        }
+                        Err(error) if is_other_configuration(&error) => {}
+                        Err(error) => return Err(loading_error(error, path)),
+                    }
+                )*
+                unsupported_stored_configuration(path)
+            }
+        }
+    };
+}
+
+macro_rules! dispatch_variants {
+    (
+        ($sketching:expr, $inner:ident => $body:expr)
+        [$(($variant:ident, $precision:ty, $bits:literal)),* $(,)?]
+    ) => {
+        match $sketching {
+            $(DynamicHyperSketching::$variant($inner) => $body,)*
+        }
+    };
+}
+
+/// Evaluates the body with the HyperSketching of the variant bound to the identifier.
+macro_rules! dispatch {
+    ($sketching:expr, $inner:ident => $body:expr) => {
+        with_configurations!(dispatch_variants!($sketching, $inner => $body))
+    };
+}
+
+with_configurations!(define_dynamic_hyper_sketching!());
+
+/// Returns whether the error of loading stored data is only due to the type
+/// hash recorded in its header, i.e. to data of another configuration.
+fn is_other_configuration(error: &anyhow::Error) -> bool {
+    matches!(
+        error.downcast_ref::<deser::Error>(),
+        Some(deser::Error::WrongTypeHash { .. })
+    )
+}
+
+/// Returns the error of loading the data stored at the path.
+fn loading_error(error: anyhow::Error, path: &Path) -> anyhow::Error {
+    error.context(format!(
+        "Error while loading hypersketching data from {}",
+        path.display()
+    ))
+}
+
+/// Returns the error for stored data whose configuration is not supported.
+fn unsupported_stored_configuration<T>(path: &Path) -> Result<T> {
+    bail!(
+        "The data stored in {} was not fitted with any of the supported configurations {:?}.",
+        path.display(),
+        SUPPORTED_CONFIGURATIONS
+    )
+}
+
+/// Returns the error for a configuration which is not supported.
+fn unsupported_configuration<T>(precision: usize, bits: usize) -> Result<T> {
+    bail!(
+        "The precision {} with {} bits is not supported, the supported configurations are {:?}.",
+        precision,
+        bits,
+        SUPPORTED_CONFIGURATIONS
+    )
+}
+
+impl<G: Graph, D: Storage> DynamicHyperSketching<G, D> {
+    /// Returns the precision of the counters.
+    pub fn precision(&self) -> usize {
+        dispatch!(self, sketching => sketching.precision())
+    }
+
+    /// Returns the number of bits of each register of the counters.
+    pub fn bits(&self) -> usize {
+        dispatch!(self, sketching => sketching.bits())
+    }
+
+    /// Returns the number of hops.
+    pub fn number_of_hops(&self) -> usize {
+        dispatch!(self, sketching => sketching.number_of_hops())
+    }
+
+    /// Returns the hops whose counters are retained.
+    pub fn retained_hops(&self) -> &[usize] {
+        dispatch!(self, sketching => sketching.retained_hops())
+    }
+
+    /// Returns whether the cardinalities are normalized.
+    pub fn normalize(&self) -> bool {
+        dispatch!(self, sketching => sketching.normalize())
+    }
+
+    /// Returns the hash of the graph the sketches were fitted on, if any.
+    pub fn graph_hash(&self) -> Option<u64> {
+        dispatch!(self, sketching => sketching.graph_hash())
+    }
+
+    /// Returns the layout of the features of each edge.
+    pub fn feature_layout(&self) -> FeatureLayout {
+        dispatch!(self, sketching => sketching.feature_layout())
+    }
+
+    /// Returns the number of features of each edge.
+    pub fn feature_len(&self) -> usize {
+        dispatch!(self, sketching => sketching.feature_len())
+    }
+
+    /// Returns the names of the features of each edge.
+    pub fn feature_names(&self) -> Vec<String> {
+        dispatch!(self, sketching => sketching.feature_names())
+    }
+
+    /// Returns the names of the features of each node.
+    pub fn node_feature_names(&self) -> Vec<String> {
+        dispatch!(self, sketching => sketching.node_feature_names())
+    }
+
+    /// Returns the row-major matrix of shape `nodes × retained hops` with the
+    /// estimated cardinalities of the balls of each node.
+    pub fn node_features_matrix(&self) -> Vec<f32> {
+        dispatch!(self, sketching => sketching.node_features_matrix())
+    }
+
+    /// Returns the graph-level statistics estimated by the counters.
+    pub fn statistics(&self) -> Result<GraphStatistics> {
+        dispatch!(self, sketching => sketching.statistics())
+    }
+
+    /// Stores the data of the model, to be loaded back or memory-mapped
+    /// with the configuration recorded in the header.
+    pub fn store<PP>(&self, path: PP) -> Result<()>
+    where
+        PP: AsRef<Path>,
+    {
+        dispatch!(self, sketching => sketching.store(path))
+    }
+}
+
+impl<G: Graph> DynamicHyperSketching<G> {
+    /// Sets the hops whose counters are retained by the next fit, and drops
+    /// the current counters.
+    pub fn set_retained_hops(&mut self, retained_hops: &[usize]) -> Result<()> {
+        dispatch!(self, sketching => sketching.set_retained_hops(retained_hops))
+    }
+
+    /// Sets whether to normalize the cardinalities.
+    pub fn set_normalize(&mut self, normalize: bool) {
+        dispatch!(self, sketching => sketching.set_normalize(normalize))
+    }
+}
+
+impl<G> DynamicHyperSketching<G>
+where
+    G: Successors + Send + Sync,
+    <G as Graph>::Nodes: IntoIndexedParallelIterator<Item = G::Node>,
+{
+    /// Fit the HyperBall model to the provided support.
+    pub fn fit(&mut self) {
+        dispatch!(self, sketching => sketching.fit())
+    }
+
+    /// Updates the sketches after the insertion of new edges, replacing the
+    /// support with the updated graph.
+    pub fn insert_edges(&mut self, graph: G, edges: &[(G::Node, G::Node)]) -> Result<()> {
+        dispatch!(self, sketching => sketching.insert_edges(graph, edges))
+    }
+}
+
+impl<G, D> DynamicHyperSketching<G, D>
+where
+    G: Successors + Sync,
+    D: Storage,
+{
+    /// Writes the biased features of the edge into the zeroed target.
+    pub fn biased_edge_features<const INSERT_EDGE: bool>(
+        &self,
+        edge: (G::Node, G::Node),
+        target: &mut [f32],
+    ) {
+        dispatch!(self, sketching => {
+            sketching.biased_edge_features::<INSERT_EDGE>(edge, target)
+        })
+    }
+
+    /// Writes the bias-aware features of the edge into the zeroed target.
+    pub fn bias_aware_edge_features<const INSERT_EDGE: bool>(
+        &self,
+        edge: (G::Node, G::Node),
+        target: &mut [f32],
+    ) {
+        dispatch!(self, sketching => {
+            sketching.bias_aware_edge_features::<INSERT_EDGE>(edge, target)
+        })
+    }
+
+    /// Returns the extractor of the biased edge features, to compute them
+    /// for batches of edges.
+    pub fn biased_features<const INSERT_EDGE: bool>(
+        &self,
+    ) -> DynamicEdgeFeatures<'_, G, INSERT_EDGE, D> {
+        DynamicEdgeFeatures {
+            sketching: self,
+            bias_aware: false,
+        }
+    }
+
+    /// Returns the extractor of the bias-aware edge features, to compute
+    /// them for batches of edges.
+    pub fn bias_aware_features<const INSERT_EDGE: bool>(
+        &self,
+    ) -> DynamicEdgeFeatures<'_, G, INSERT_EDGE, D> {
+        DynamicEdgeFeatures {
+            sketching: self,
+            bias_aware: true,
+        }
+    }
+
+    /// Returns the accuracy of the biased features estimated by the counters
+    /// over the provided edges, to choose the configuration.
+    pub fn accuracy_report<I>(&self, edges: I) -> AccuracyReport
+    where
+        I: IntoParallelIterator<Item = (G::Node, G::Node)>,
+        I::Iter: IndexedParallelIterator,
+    {
+        dispatch!(self, sketching => sketching.accuracy_report(edges))
+    }
+}
+
+/// The biased or bias-aware edge features of a [`DynamicHyperSketching`].
+pub struct DynamicEdgeFeatures<'a, G, const INSERT_EDGE: bool, D: Storage = Owned> {
+    sketching: &'a DynamicHyperSketching<G, D>,
+    bias_aware: bool,
+}
+
+impl<G, const INSERT_EDGE: bool, D> EdgeFeatures for DynamicEdgeFeatures<'_, G, INSERT_EDGE, D>
+where
+    G: Successors + Sync,
+    D: Storage,
+{
+    type Node = G::Node;
+
+    #[inline(always)]
+    fn feature_len(&self) -> usize {
+        self.sketching.feature_len()
+    }
+
+    #[inline(always)]
+    fn feature_names(&self) -> Vec<String> {
+        self.sketching.feature_names()
+    }
+
+    #[inline(always)]
+    fn edge_features(&self, edge: (G::Node, G::Node), target: &mut [f32]) {
+        if self.bias_aware {
+            self.sketching
+                .bias_aware_edge_features::<INSERT_EDGE>(edge, target)
+        } else {
+            self.sketching
+                .biased_edge_features::<INSERT_EDGE>(edge, target)
+        }
+    }
+
+    /// Writes the features of the edges in parallel with the extractor of
+    /// the variant, which allocates the masks of the bias-aware visits once
+    /// for each rayon task.
+    fn par_edge_features<I>(&self, edges: I, target: &mut [f32])
+    where
+        I: IntoParallelIterator<Item = (G::Node, G::Node)>,
+        I::Iter: IndexedParallelIterator,
+    {
+        dispatch!(self.sketching, sketching => {
+            if self.bias_aware {
+                sketching
+                    .bias_aware_features::<INSERT_EDGE>()
+                    .par_edge_features(edges, target)
+            } else {
+                sketching
+                    .biased_features::<INSERT_EDGE>()
+                    .par_edge_features(edges, target)
+            }
+        })
+    }
+}
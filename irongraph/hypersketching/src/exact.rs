@@ -0,0 +1,362 @@
+//! Module providing the exact overlap features, computed with explicit
+//! breadth-first visits, as a reference to measure how far the estimates
+//! of the sketches deviate from the truth.
+//!
+//! The features have the same layout as the ones of [`HyperSketching`]: the
+//! hop 0 of a node holds the node itself and its successors, the hop `i` the
+//! nodes at distance `i + 1`, and the overlap matrix of the hops of the two
+//! endpoints is followed by their left and right differences.
+use crate::layout::FeatureLayout;
+use crate::sketches::SetSketch;
+use crate::{retained_hop_index, HyperSketching, HyperSketchingData, Normalized, Normalizer};
+use algorithms::features::EdgeFeatures;
+use anyhow::{ensure, Result};
+use core::fmt::{Display, Formatter};
+use graph::Successors;
+use irontraits::{Sequence, To};
+use rayon::prelude::*;
+use std::collections::hash_map::{Entry, HashMap};
+
+/// The exact overlap features of the edges of a graph.
+///
+/// As for the biased features of [`HyperSketching`], the edge itself is not
+/// removed from the graph before the visits.
+#[derive(Debug, Clone)]
+pub struct ExactOverlapFeatures<'a, G> {
+    /// The number of hops of the visits.
+    number_of_hops: usize,
+    /// The hops whose balls the features are computed on, as for the
+    /// retained hops of [`HyperSketching`].
+    retained_hops: Vec<usize>,
+    /// Whether to normalize the cardinalities, as the bias-aware features do.
+    normalize: bool,
+    /// The graph to visit.
+    graph: &'a G,
+}
+
+impl<'a, G: Successors> ExactOverlapFeatures<'a, G> {
+    /// Creates the exact overlap features of the edges of the graph.
+    ///
+    /// # Arguments
+    /// * `number_of_hops`: usize - The number of hops of the visits.
+    /// * `normalize`: bool - Whether to normalize the cardinalities.
+    /// * `graph`: &G - The graph to visit.
+    pub fn new(number_of_hops: usize, normalize: bool, graph: &'a G) -> Result<Self> {
+        ensure!(
+            number_of_hops > 0,
+            "The number of hops must be greater than 0."
+        );
+        Ok(Self {
+            number_of_hops,
+            retained_hops: (0..number_of_hops).collect(),
+            normalize,
+            graph,
+        })
+    }
+
+    /// Returns the number of hops.
+    #[inline(always)]
+    pub fn number_of_hops(&self) -> usize {
+        self.number_of_hops
+    }
+
+    /// Returns whether the cardinalities are normalized.
+    #[inline(always)]
+    pub fn normalize(&self) -> bool {
+        self.normalize
+    }
+
+    /// Returns the hop of each node within the number of hops from the root.
+    fn hops(&self, root: G::Node) -> HashMap<usize, usize> {
+        let mut hops = HashMap::from([(root.to(), 0)]);
+        let mut frontier = vec![root];
+        for hop in 0..self.number_of_hops {
+            let mut next = Vec::new();
+            for node in frontier {
+                for successor in self.graph.successors(node) {
+                    if let Entry::Vacant(entry) = hops.entry(successor.to()) {
+                        entry.insert(hop);
+                        next.push(successor);
+                    }
+                }
+            }
+            frontier = next;
+        }
+        hops
+    }
+}
+
+impl<G> EdgeFeatures for ExactOverlapFeatures<'_, G>
+where
+    G: Successors + Sync,
+{
+    type Node = G::Node;
+
+    #[inline(always)]
+    fn feature_len(&self) -> usize {
+        FeatureLayout::new(&self.retained_hops).len()
+    }
+
+    #[inline(always)]
+    fn feature_names(&self) -> Vec<String> {
+        FeatureLayout::new(&self.retained_hops).names()
+    }
+
+    fn edge_features(&self, (src, dst): (G::Node, G::Node), target: &mut [f32]) {
+        let hops = self.retained_hops.len();
+        let retained = |hop: usize| retained_hop_index(&self.retained_hops, hop);
+        let left = self.hops(src);
+        let right = self.hops(dst);
+
+        let (overlaps, differences) = target.split_at_mut(hops * hops);
+        let (left_difference, right_difference) = differences.split_at_mut(hops);
+
+        for (node, &i) in &left {
+            match right.get(node) {
+                Some(&j) => overlaps[retained(i) * hops + retained(j)] += 1.0,
+                None => left_difference[retained(i)] += 1.0,
+            }
+        }
+        for (node, &j) in &right {
+            if !left.contains_key(node) {
+                right_difference[retained(j)] += 1.0;
+            }
+        }
+
+        if self.normalize {
+            let mut normalizer = Normalized::new(hops);
+            left.values()
+                .for_each(|&i| normalizer.inc_left_cardinality(retained(i), 1.0));
+            right
+                .values()
+                .for_each(|&j| normalizer.inc_right_cardinality(retained(j), 1.0));
+            normalizer.normalize(overlaps, left_difference, right_difference);
+        }
+    }
+}
+
+/// The errors of the estimates of a feature with respect to its exact value.
+#[derive(Debug, Clone)]
+pub struct FeatureAccuracy {
+    name: String,
+    mean_exact_value: f64,
+    mean_absolute_error: f64,
+    root_mean_squared_error: f64,
+    maximum_absolute_error: f64,
+}
+
+impl FeatureAccuracy {
+    /// Returns the name of the feature.
+    #[inline(always)]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the mean of the exact values of the feature, to put the errors in scale.
+    #[inline(always)]
+    pub fn mean_exact_value(&self) -> f64 {
+        self.mean_exact_value
+    }
+
+    /// Returns the mean absolute error of the estimates.
+    #[inline(always)]
+    pub fn mean_absolute_error(&self) -> f64 {
+        self.mean_absolute_error
+    }
+
+    /// Returns the root mean squared error of the estimates.
+    #[inline(always)]
+    pub fn root_mean_squared_error(&self) -> f64 {
+        self.root_mean_squared_error
+    }
+
+    /// Returns the largest absolute error of the estimates.
+    #[inline(always)]
+    pub fn maximum_absolute_error(&self) -> f64 {
+        self.maximum_absolute_error
+    }
+}
+
+/// The accuracy of the features estimated by the counters of a
+/// [`HyperSketching`], with respect to the exact features, over a set of edges.
+#[derive(Debug, Clone)]
+pub struct AccuracyReport {
+    precision: usize,
+    bits: usize,
+    number_of_edges: usize,
+    features: Vec<FeatureAccuracy>,
+}
+
+impl AccuracyReport {
+    /// Returns the report of the estimated features against the exact
+    /// ones, both as row-major matrices of shape `edges × names`.
+    fn new(
+        precision: usize,
+        bits: usize,
+        names: Vec<String>,
+        estimated: &[f32],
+        exact: &[f32],
+    ) -> Self {
+        let feature_len = names.len();
+        let number_of_edges = exact.len() / feature_len;
+        let denominator = number_of_edges.max(1) as f64;
+        let features = names
+            .into_iter()
+            .enumerate()
+            .map(|(feature, name)| {
+                let (mut exact_sum, mut absolute_sum, mut squared_sum, mut maximum) =
+                    (0.0, 0.0, 0.0, 0.0_f64);
+                for (estimated_row, exact_row) in estimated
+                    .chunks_exact(feature_len)
+                    .zip(exact.chunks_exact(feature_len))
+                {
+                    let error = (estimated_row[feature] - exact_row[feature]).abs() as f64;
+                    exact_sum += exact_row[feature] as f64;
+                    absolute_sum += error;
+                    squared_sum += error * error;
+                    maximum = maximum.max(error);
+                }
+                FeatureAccuracy {
+                    name,
+                    mean_exact_value: exact_sum / denominator,
+                    mean_absolute_error: absolute_sum / denominator,
+                    root_mean_squared_error: (squared_sum / denominator).sqrt(),
+                    maximum_absolute_error: maximum,
+                }
+            })
+            .collect();
+
+        Self {
+            precision,
+            bits,
+            number_of_edges,
+            features,
+        }
+    }
+
+    /// Returns the precision of the sketches, e.g. the base-2 logarithm of
+    /// the number of registers of HyperLogLog counters.
+    #[inline(always)]
+    pub fn precision(&self) -> usize {
+        self.precision
+    }
+
+    /// Returns the number of bits of each register of the sketches.
+    #[inline(always)]
+    pub fn bits(&self) -> usize {
+        self.bits
+    }
+
+    /// Returns the number of edges the features were compared on.
+    #[inline(always)]
+    pub fn number_of_edges(&self) -> usize {
+        self.number_of_edges
+    }
+
+    /// Returns the accuracy of each feature, in the order of the features.
+    #[inline(always)]
+    pub fn features(&self) -> &[FeatureAccuracy] {
+        &self.features
+    }
+
+    /// Returns the mean absolute error over all the features.
+    pub fn mean_absolute_error(&self) -> f64 {
+        self.features
+            .iter()
+            .map(FeatureAccuracy::mean_absolute_error)
+            .sum::<f64>()
+            / self.features.len().max(1) as f64
+    }
+
+    /// Returns the largest absolute error over all the features.
+    pub fn maximum_absolute_error(&self) -> f64 {
+        self.features
+            .iter()
+            .map(FeatureAccuracy::maximum_absolute_error)
+            .fold(0.0, f64::max)
+    }
+}
+
+impl Display for AccuracyReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        writeln!(
+            f,
+            "Accuracy of precision {} with {} bits over {} edges:",
+            self.precision, self.bits, self.number_of_edges
+        )?;
+        for feature in &self.features {
+            writeln!(
+                f,
+                "{}: mean {:.3}, MAE {:.3}, RMSE {:.3}, max error {:.3}",
+                feature.name,
+                feature.mean_exact_value,
+                feature.mean_absolute_error,
+                feature.root_mean_squared_error,
+                feature.maximum_absolute_error
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<G, Data, Counters, S> HyperSketching<G, Data, Counters, S>
+where
+    G: Successors + Sync,
+    Counters: Sequence<Item = S> + AsRef<[S]> + Sync,
+    S: SetSketch,
+    Data: AsRef<HyperSketchingData<Counters>> + Sync,
+{
+    /// Returns the exact features of the edges of the support, with the
+    /// hops and the normalization of the model.
+    #[inline(always)]
+    pub fn exact_features(&self) -> ExactOverlapFeatures<'_, G> {
+        ExactOverlapFeatures {
+            number_of_hops: self.number_of_hops(),
+            retained_hops: self.retained_hops().to_vec(),
+            normalize: self.normalize(),
+            graph: &self.graph,
+        }
+    }
+
+    /// Returns the accuracy of the biased features estimated by the counters
+    /// over the provided edges, usually a sample of the edges to predict.
+    ///
+    /// The errors are measured on the cardinalities before normalization, as
+    /// the normalization of the estimates relies on estimated cardinalities.
+    ///
+    /// # Arguments
+    /// * `edges`: I - The edges, as an indexed parallel iterator or a collection providing one.
+    pub fn accuracy_report<I>(&self, edges: I) -> AccuracyReport
+    where
+        I: IntoParallelIterator<Item = (G::Node, G::Node)>,
+        I::Iter: IndexedParallelIterator,
+    {
+        let edges = edges.into_par_iter();
+        let feature_len = self.feature_len();
+        let exact_features = ExactOverlapFeatures {
+            number_of_hops: self.number_of_hops(),
+            retained_hops: self.retained_hops().to_vec(),
+            normalize: false,
+            graph: &self.graph,
+        };
+
+        let mut estimated = vec![0.0; edges.len() * feature_len];
+        let mut exact = vec![0.0; edges.len() * feature_len];
+        estimated
+            .par_chunks_exact_mut(feature_len)
+            .zip(exact.par_chunks_exact_mut(feature_len))
+            .zip(edges)
+            .for_each(|((estimated, exact), edge)| {
+                self.biased_edge_features_dispatched(edge, false, estimated);
+                exact_features.edge_features(edge, exact);
+            });
+
+        AccuracyReport::new(
+            S::PRECISION,
+            S::BITS,
+            self.feature_names(),
+            &estimated,
+            &exact,
+        )
+    }
+}
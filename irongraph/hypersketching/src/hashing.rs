@@ -0,0 +1,125 @@
+//! Module providing the hashes which are stored along with the sketches, so
+//! that they do not depend on the platform nor on the release of Rust.
+//!
+//! The hashes are XXH3 digests of fixed-width little-endian encodings,
+//! mixed with SplitMix64 where several independent hashes are needed.
+use core::hash::Hasher;
+use graph::Successors;
+use irontraits::To;
+use xxhash_rust::xxh3::Xxh3;
+
+/// A hasher writing the integers as little-endian bytes into an XXH3 digest,
+/// with `usize` and `isize` widened to 64 bits, so that the hash of a value
+/// is the same on every platform and release of Rust.
+pub(crate) struct StableHasher(Xxh3);
+
+impl Default for StableHasher {
+    #[inline(always)]
+    fn default() -> Self {
+        Self(Xxh3::new())
+    }
+}
+
+impl Hasher for StableHasher {
+    #[inline(always)]
+    fn finish(&self) -> u64 {
+        self.0.digest()
+    }
+
+    #[inline(always)]
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    #[inline(always)]
+    fn write_u8(&mut self, value: u8) {
+        self.write(&[value]);
+    }
+
+    #[inline(always)]
+    fn write_u16(&mut self, value: u16) {
+        self.write(&value.to_le_bytes());
+    }
+
+    #[inline(always)]
+    fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    #[inline(always)]
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    #[inline(always)]
+    fn write_u128(&mut self, value: u128) {
+        self.write(&value.to_le_bytes());
+    }
+
+    #[inline(always)]
+    fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64);
+    }
+
+    #[inline(always)]
+    fn write_i8(&mut self, value: i8) {
+        self.write_u8(value as u8);
+    }
+
+    #[inline(always)]
+    fn write_i16(&mut self, value: i16) {
+        self.write_u16(value as u16);
+    }
+
+    #[inline(always)]
+    fn write_i32(&mut self, value: i32) {
+        self.write_u32(value as u32);
+    }
+
+    #[inline(always)]
+    fn write_i64(&mut self, value: i64) {
+        self.write_u64(value as u64);
+    }
+
+    #[inline(always)]
+    fn write_i128(&mut self, value: i128) {
+        self.write_u128(value as u128);
+    }
+
+    #[inline(always)]
+    fn write_isize(&mut self, value: isize) {
+        self.write_i64(value as i64);
+    }
+}
+
+/// Returns the provided value mixed with the SplitMix64 finalizer, which is
+/// a bijection on u64.
+#[inline(always)]
+pub(crate) fn splitmix64(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Returns the hash of the graph recorded at fit time, which is checked
+/// when the sketches are loaded back with a graph.
+///
+/// The hash is the XXH3 digest of the number of nodes, followed for each
+/// node by its successors and by its number of successors, each as a
+/// little-endian u64. It only depends on the edges of the graph, so that a
+/// graph and its memory-mapped copy have the same hash.
+pub(crate) fn hash_graph<G: Successors>(graph: &G) -> u64 {
+    let mut hasher = StableHasher::default();
+    hasher.write_usize(graph.number_of_nodes());
+    for node in graph.nodes() {
+        let mut degree = 0;
+        for successor in graph.successors(node) {
+            let successor: usize = successor.to();
+            hasher.write_usize(successor);
+            degree += 1;
+        }
+        hasher.write_usize(degree);
+    }
+    hasher.finish()
+}
@@ -0,0 +1,183 @@
+//! Module providing the layout of the edge features of a [`HyperSketching`](crate::HyperSketching),
+//! to label and slice the columns of the flat feature rows.
+//!
+//! The features of an edge are the row-major overlap matrix of the retained
+//! hops of its source and destination, followed by the left differences and by
+//! the right differences of the retained hops. The columns are named after the
+//! hops, e.g. `overlap_h1_h2`, `left_diff_h0` and `right_diff_h2`.
+
+/// The layout of the edge features computed on the provided hops.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FeatureLayout {
+    /// The hops the features are computed on, sorted.
+    hops: Vec<usize>,
+}
+
+impl FeatureLayout {
+    /// Creates the layout of the edge features computed on the provided hops.
+    ///
+    /// # Arguments
+    /// * `hops`: &[usize] - The sorted hops the features are computed on, e.g. the retained hops.
+    ///
+    /// # Panics
+    /// If the hops are not strictly increasing.
+    pub fn new(hops: &[usize]) -> Self {
+        assert!(
+            hops.windows(2).all(|pair| pair[0] < pair[1]),
+            "The hops must be strictly increasing."
+        );
+        Self {
+            hops: hops.to_vec(),
+        }
+    }
+
+    /// Returns the hops the features are computed on.
+    #[inline(always)]
+    pub fn hops(&self) -> &[usize] {
+        &self.hops
+    }
+
+    /// Returns the number of features of each edge.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        let hops = self.hops.len();
+        hops * hops + 2 * hops
+    }
+
+    /// Returns whether there are no features, i.e. no hops.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.hops.is_empty()
+    }
+
+    /// Returns the names of the features of each edge, in the order in which
+    /// they are written.
+    pub fn names(&self) -> Vec<String> {
+        let mut names = Vec::with_capacity(self.len());
+        for i in &self.hops {
+            for j in &self.hops {
+                names.push(format!("overlap_h{}_h{}", i, j));
+            }
+        }
+        names.extend(self.hops.iter().map(|i| format!("left_diff_h{}", i)));
+        names.extend(self.hops.iter().map(|i| format!("right_diff_h{}", i)));
+        names
+    }
+
+    /// Returns the position of the hop among the hops of the layout, if any.
+    #[inline(always)]
+    fn position(&self, hop: usize) -> Option<usize> {
+        self.hops.binary_search(&hop).ok()
+    }
+
+    /// Returns the column of the overlap of the provided hop of the source
+    /// with the provided hop of the destination, if both are in the layout.
+    pub fn overlap_column(&self, source_hop: usize, destination_hop: usize) -> Option<usize> {
+        Some(self.position(source_hop)? * self.hops.len() + self.position(destination_hop)?)
+    }
+
+    /// Returns the column of the left difference of the provided hop, if it is in the layout.
+    pub fn left_difference_column(&self, hop: usize) -> Option<usize> {
+        Some(self.hops.len() * self.hops.len() + self.position(hop)?)
+    }
+
+    /// Returns the column of the right difference of the provided hop, if it is in the layout.
+    pub fn right_difference_column(&self, hop: usize) -> Option<usize> {
+        Some(self.hops.len() * (self.hops.len() + 1) + self.position(hop)?)
+    }
+
+    /// Returns the column of the feature with the provided name, if any.
+    ///
+    /// The hops are parsed from the name, which must be written as by
+    /// [`names`](Self::names), e.g. `overlap_h1_h2` and not `overlap_h01_h2`.
+    pub fn column(&self, name: &str) -> Option<usize> {
+        if let Some(hops) = name.strip_prefix("overlap_h") {
+            let (source_hop, destination_hop) = hops.split_once("_h")?;
+            self.overlap_column(parse_hop(source_hop)?, parse_hop(destination_hop)?)
+        } else if let Some(hop) = name.strip_prefix("left_diff_h") {
+            self.left_difference_column(parse_hop(hop)?)
+        } else if let Some(hop) = name.strip_prefix("right_diff_h") {
+            self.right_difference_column(parse_hop(hop)?)
+        } else {
+            None
+        }
+    }
+
+    /// Splits the features of an edge into the row-major overlap matrix, the
+    /// left differences and the right differences.
+    ///
+    /// # Arguments
+    /// * `features`: &[f32] - The features of an edge.
+    ///
+    /// # Panics
+    /// If the features do not have the length of the layout.
+    pub fn split<'a>(&self, features: &'a [f32]) -> (&'a [f32], &'a [f32], &'a [f32]) {
+        assert_eq!(
+            features.len(),
+            self.len(),
+            "The features must have the length of the layout."
+        );
+        let hops = self.hops.len();
+        let (overlaps, differences) = features.split_at(hops * hops);
+        let (left_differences, right_differences) = differences.split_at(hops);
+        (overlaps, left_differences, right_differences)
+    }
+
+    /// Splits the features of an edge into the mutable row-major overlap
+    /// matrix, left differences and right differences.
+    ///
+    /// # Arguments
+    /// * `features`: &mut [f32] - The features of an edge.
+    ///
+    /// # Panics
+    /// If the features do not have the length of the layout.
+    pub fn split_mut<'a>(
+        &self,
+        features: &'a mut [f32],
+    ) -> (&'a mut [f32], &'a mut [f32], &'a mut [f32]) {
+        assert_eq!(
+            features.len(),
+            self.len(),
+            "The features must have the length of the layout."
+        );
+        let hops = self.hops.len();
+        let (overlaps, differences) = features.split_at_mut(hops * hops);
+        let (left_differences, right_differences) = differences.split_at_mut(hops);
+        (overlaps, left_differences, right_differences)
+    }
+
+    /// Returns the row-major overlap matrix of the features of an edge.
+    ///
+    /// # Panics
+    /// If the features do not have the length of the layout.
+    #[inline(always)]
+    pub fn overlaps<'a>(&self, features: &'a [f32]) -> &'a [f32] {
+        self.split(features).0
+    }
+
+    /// Returns the left differences of the features of an edge.
+    ///
+    /// # Panics
+    /// If the features do not have the length of the layout.
+    #[inline(always)]
+    pub fn left_differences<'a>(&self, features: &'a [f32]) -> &'a [f32] {
+        self.split(features).1
+    }
+
+    /// Returns the right differences of the features of an edge.
+    ///
+    /// # Panics
+    /// If the features do not have the length of the layout.
+    #[inline(always)]
+    pub fn right_differences<'a>(&self, features: &'a [f32]) -> &'a [f32] {
+        self.split(features).2
+    }
+}
+
+/// Parses a hop written in a feature name, rejecting the spellings which
+/// are not written by [`FeatureLayout::names`], e.g. `+1` or `01`.
+fn parse_hop(hop: &str) -> Option<usize> {
+    hop.parse::<usize>()
+        .ok()
+        .filter(|parsed| parsed.to_string() == hop)
+}
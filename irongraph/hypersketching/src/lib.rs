@@ -1,18 +1,292 @@
-use anyhow::{ensure, Context, Result};
-use core::hash::{Hash, Hasher};
+use algorithms::features::EdgeFeatures;
+use anyhow::{bail, ensure, Context, Result};
 use epserde::{
     deser::{Deserialize, DeserializeInner, MemCase},
+    ser::Serialize,
     traits::TypeHash,
     Epserde,
 };
 use graph::{Graph, Successors};
+use hashing::hash_graph;
 use hyperloglog_rs::prelude::*;
 use irontraits::{IntoIndexedParallelIterator, Sequence, SequenceAllocable, To};
-use par_replica::ParReplica;
+use layout::FeatureLayout;
 use rayon::prelude::*;
-use std::{marker::PhantomData, path::Path};
+use sketches::SetSketch;
+use std::{collections::HashSet, marker::PhantomData, path::Path};
 use sux::prelude::{BitFieldSlice, BitFieldSliceMut, BitFieldVec};
 
+pub mod directed;
+pub mod dynamic;
+pub mod exact;
+mod hashing;
+pub mod layout;
+pub mod out_of_core;
+pub mod sketches;
+pub mod statistics;
+
+/// Returns the index among the retained hops of the ball the hop belongs to,
+/// i.e. of the first retained hop not smaller than it.
+#[inline(always)]
+fn retained_hop_index(retained_hops: &[usize], hop: usize) -> usize {
+    retained_hops.partition_point(|&retained_hop| retained_hop < hop)
+}
+
+/// Returns the sketch of the first hop of the node, i.e. of the node and its successors.
+fn first_hop_sketch<G, S>(graph: &G, node: G::Node) -> S
+where
+    G: Successors,
+    S: SetSketch,
+{
+    let mut sketch = S::default();
+    sketch.insert_element(node);
+    for successor in graph.successors(node) {
+        sketch.insert_element(successor);
+    }
+    sketch
+}
+
+/// Returns the sketch of the next hop of the node, i.e. the union of its sketch
+/// of the previous hop with the ones of its successors.
+///
+/// # Arguments
+/// * `graph`: &G - The graph to visit.
+/// * `node`: G::Node - The node whose sketch is computed.
+/// * `previous_counter`: &S - The sketch of the previous hop of the node.
+/// * `previous_hop`: F - Returns the sketch of the previous hop of the successor with the provided index.
+fn next_hop_sketch<'a, G, S, F>(
+    graph: &G,
+    node: G::Node,
+    previous_counter: &S,
+    previous_hop: F,
+) -> S
+where
+    G: Successors,
+    S: SetSketch + 'a,
+    F: Fn(usize) -> &'a S,
+{
+    let mut sketch = previous_counter.clone();
+    for successor in graph.successors(node) {
+        sketch.union_assign(previous_hop(successor.to()));
+    }
+    sketch
+}
+
+/// Fits the sketches of the retained hops of every node of the graph into
+/// the counters, a row of one counter for each retained hop for each node.
+///
+/// The hop `k` of every node is computed in parallel from the hop `k - 1`,
+/// so that the counters are accessed one hop at a time. When only some of the
+/// hops are retained, the other hops are computed in the counters of the next
+/// retained hop and in a spare buffer, as by [`fit_retained_counters`].
+fn fit_counters<G, S>(graph: &G, counters: &mut [S], number_of_hops: usize, retained_hops: &[usize])
+where
+    G: Successors + Sync,
+    <G as Graph>::Nodes: IntoIndexedParallelIterator<Item = G::Node>,
+    S: SetSketch,
+{
+    if retained_hops.len() < number_of_hops {
+        fit_retained_counters(graph, counters, number_of_hops, retained_hops);
+        return;
+    }
+
+    // Create the sketches of the first hop for all nodes in the graph
+    graph
+        .nodes()
+        .into_par_iter()
+        .zip(
+            counters
+                .par_chunks_exact_mut(number_of_hops)
+                .map(|counters| &mut counters[0]),
+        )
+        .for_each(|(node, counter): (G::Node, &mut S)| {
+            *counter = first_hop_sketch(graph, node);
+        });
+
+    let counters: &[S] = counters;
+    // Iterate over all hops and update the counters accordingly
+    (1..number_of_hops).for_each(|k| {
+        graph
+            .nodes()
+            .into_par_iter()
+            .zip(counters.par_chunks_exact(number_of_hops).map(|row| {
+                let (previous, current) = row.split_at(k);
+                let current = unsafe { core::mem::transmute_copy::<_, &mut [S]>(&current) };
+                (&previous[k - 1], &mut current[0])
+            }))
+            .for_each(|(node, (previous_counter, current_counter))| {
+                // Iterate over all neighbors of the current node
+                *current_counter = next_hop_sketch(graph, node, previous_counter, |successor| {
+                    &counters[successor * number_of_hops + k - 1]
+                });
+            });
+    });
+}
+
+/// Writes the sketches of the hop `k` of every node into the targets, from
+/// the sketches of the hop `k - 1` of the nodes with the provided index.
+fn fit_hop<'a, G, S, F>(
+    graph: &G,
+    k: usize,
+    targets: impl IndexedParallelIterator<Item = &'a mut S>,
+    previous_hop: F,
+) where
+    G: Successors + Sync,
+    <G as Graph>::Nodes: IntoIndexedParallelIterator<Item = G::Node>,
+    S: SetSketch + 'a,
+    F: Fn(usize) -> &'a S + Sync,
+{
+    graph
+        .nodes()
+        .into_par_iter()
+        .zip(targets)
+        .for_each(|(node, counter)| {
+            *counter = if k == 0 {
+                first_hop_sketch(graph, node)
+            } else {
+                let node_index: usize = node.to();
+                next_hop_sketch(graph, node, previous_hop(node_index), &previous_hop)
+            };
+        });
+}
+
+/// Fits the sketches of the retained hops with two rolling buffers, holding
+/// the counters of the previous and of the current hop of every node.
+///
+/// One of the buffers is the column of the counters of the next retained
+/// hop, which is free until the hop is fitted, and the other one is a spare
+/// vector of one counter for each node. At most `nodes × (retained hops + 1)`
+/// counters are thus allocated while fitting, e.g. two counters for each node
+/// when only the last hop is retained.
+fn fit_retained_counters<G, S>(
+    graph: &G,
+    counters: &mut [S],
+    number_of_hops: usize,
+    retained_hops: &[usize],
+) where
+    G: Successors + Sync,
+    <G as Graph>::Nodes: IntoIndexedParallelIterator<Item = G::Node>,
+    S: SetSketch,
+{
+    let row_len = retained_hops.len();
+    let mut spare = vec![S::default(); graph.number_of_nodes()];
+    // The column of the counters holding the previous hop, if it is not in the spare vector.
+    let mut previous_column: Option<usize> = None;
+
+    for k in 0..number_of_hops {
+        // The hops before the next retained one alternate between the spare
+        // vector and its column, so that the hop preceding it is in the spare vector.
+        let next = retained_hop_index(retained_hops, k);
+        let column =
+            (retained_hops[next] == k || (retained_hops[next] - 1 - k) % 2 == 1).then_some(next);
+
+        match (previous_column, column) {
+            (None, Some(column)) => fit_hop(
+                graph,
+                k,
+                counters
+                    .par_chunks_exact_mut(row_len)
+                    .map(|row| &mut row[column]),
+                |successor| &spare[successor],
+            ),
+            _ => {
+                debug_assert!(k == 0 || previous_column.is_some());
+                let previous_column = previous_column.unwrap_or(0);
+                let previous: &[S] = counters;
+                fit_hop(graph, k, spare.par_iter_mut(), |successor| {
+                    &previous[successor * row_len + previous_column]
+                });
+                // The previous hop is in another column, which cannot be
+                // read while the column of the hop is written.
+                if let Some(column) = column {
+                    counters
+                        .par_chunks_exact_mut(row_len)
+                        .zip(spare.par_iter())
+                        .for_each(|(row, counter)| row[column].clone_from(counter));
+                }
+            }
+        }
+        previous_column = column;
+    }
+}
+
+/// Checks that the updated graph is the previous one with the inserted edges,
+/// before the sketches of the previous graph are updated.
+///
+/// The inserted edges must be between nodes of the updated graph and be
+/// among its edges. The edges of the previous graph must all be in the updated
+/// graph, whose other edges must be inserted ones or, when the graph is
+/// undirected, their reverse. Both graphs are visited once in parallel.
+fn check_inserted_edges<G>(previous: &G, graph: &G, edges: &[(G::Node, G::Node)]) -> Result<()>
+where
+    G: Successors + Sync,
+    <G as Graph>::Nodes: IntoIndexedParallelIterator<Item = G::Node>,
+{
+    let previous_number_of_nodes = previous.number_of_nodes();
+    let number_of_nodes = graph.number_of_nodes();
+    let mut inserted = HashSet::with_capacity(2 * edges.len());
+    for &(src, dst) in edges {
+        let (src_index, dst_index): (usize, usize) = (src.to(), dst.to());
+        ensure!(
+            src_index < number_of_nodes && dst_index < number_of_nodes,
+            "The inserted edge ({}, {}) has an endpoint outside of the {} nodes of the updated graph.",
+            src_index,
+            dst_index,
+            number_of_nodes
+        );
+        ensure!(
+            graph.has_successor(src, dst),
+            "The inserted edge ({}, {}) is not an edge of the updated graph.",
+            src_index,
+            dst_index
+        );
+        inserted.insert((src_index, dst_index));
+        if graph.undirected() {
+            inserted.insert((dst_index, src_index));
+        }
+    }
+
+    let removed_edge = previous.nodes().into_par_iter().find_map_any(|src| {
+        previous
+            .successors(src)
+            .into_iter()
+            .find(|&dst| !graph.has_successor(src, dst))
+            .map(|dst| (src, dst))
+    });
+    if let Some((src, dst)) = removed_edge {
+        let (src, dst): (usize, usize) = (src.to(), dst.to());
+        bail!(
+            "The edge ({}, {}) of the previous graph is not an edge of the updated graph.",
+            src,
+            dst
+        );
+    }
+
+    let unlisted_edge = graph.nodes().into_par_iter().find_map_any(|src| {
+        let src_index: usize = src.to();
+        graph
+            .successors(src)
+            .into_iter()
+            .find(|&dst| {
+                let dst_index: usize = dst.to();
+                let is_previous = src_index < previous_number_of_nodes
+                    && dst_index < previous_number_of_nodes
+                    && previous.has_successor(src, dst);
+                !is_previous && !inserted.contains(&(src_index, dst_index))
+            })
+            .map(|dst| (src, dst))
+    });
+    if let Some((src, dst)) = unlisted_edge {
+        let (src, dst): (usize, usize) = (src.to(), dst.to());
+        bail!(
+            "The edge ({}, {}) of the updated graph is neither a previous nor an inserted edge.",
+            src,
+            dst
+        );
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone, Epserde)]
 pub struct HyperSketchingData<Counters> {
     /// Whether to normalize the Sketching cardinalities.
@@ -22,7 +296,15 @@ pub struct HyperSketchingData<Counters> {
     /// the hash of the graph on which this was computed
     /// if it's Some it will be checked when converting to Hypersketching
     graph_hash: Option<u64>,
-    /// Vector of HyperLogLog counters
+    /// The precision of the sketches, e.g. the base-2 logarithm of the number
+    /// of registers of HyperLogLog counters, checked when converting to Hypersketching.
+    precision: usize,
+    /// The number of bits of each register of the sketches, checked when
+    /// converting to Hypersketching.
+    bits: usize,
+    /// The hops whose counters are kept, sorted and ending with the last hop.
+    retained_hops: Vec<usize>,
+    /// Vector of the sketches of the hops of each node
     counters: Counters,
 }
 
@@ -33,27 +315,31 @@ impl<Counters> AsRef<HyperSketchingData<Counters>> for HyperSketchingData<Counte
     }
 }
 
-pub struct HyperSketching<G, Data, Counters, P = Precision12, const BITS: usize = 6>
+impl<Counters> AsMut<HyperSketchingData<Counters>> for HyperSketchingData<Counters> {
+    #[inline(always)]
+    fn as_mut(&mut self) -> &mut HyperSketchingData<Counters> {
+        self
+    }
+}
+
+pub struct HyperSketching<G, Data, Counters, S = HyperLogLog<Precision12, 6>>
 where
-    P: Precision + WordType<BITS>,
-    Counters: Sequence<Item = HyperLogLog<P, BITS>>,
+    S: SetSketch,
+    Counters: Sequence<Item = S>,
 {
     /// The portion of hypersketching that can be serialized and deserialized
     data: Data,
     /// The support graph to use.
     graph: G,
-    /// Masks to be used by the various threads without reallocation.
-    masks: Option<ParReplica<BitFieldVec>>,
 
     _marker: PhantomData<Counters>,
 }
 
-impl<G, Counters, P, const BITS: usize>
-    HyperSketching<G, HyperSketchingData<Counters>, Counters, P, BITS>
+impl<G, Counters, S> HyperSketching<G, HyperSketchingData<Counters>, Counters, S>
 where
-    G: Graph + Hash,
-    Counters: SequenceAllocable<Item = HyperLogLog<P, BITS>>,
-    P: Precision + WordType<BITS>,
+    G: Graph,
+    Counters: SequenceAllocable<Item = S>,
+    S: SetSketch,
 {
     /// Creates a new HyperSketching model.
     ///
@@ -73,28 +359,55 @@ where
                 number_of_hops,
                 normalize,
                 graph_hash: None,
+                precision: S::PRECISION,
+                bits: S::BITS,
+                retained_hops: (0..number_of_hops).collect(),
             },
-            masks: None,
             graph,
             _marker: PhantomData,
         })
     }
+}
 
+impl<G, Counters, S> HyperSketching<G, HyperSketchingData<Counters>, Counters, S>
+where
+    G: Successors,
+    Counters: SequenceAllocable<Item = S>,
+    S: SetSketch,
+{
     pub unsafe fn from_data<Data>(
         data: Data,
         graph: G,
         check_graph_hash: bool,
-    ) -> Result<HyperSketching<G, Data, Counters, P, BITS>>
+    ) -> Result<HyperSketching<G, Data, Counters, S>>
     where
         Data: AsRef<HyperSketchingData<Counters>>,
     {
+        let (precision, bits) = (data.as_ref().precision, data.as_ref().bits);
+        ensure!(
+            precision == S::PRECISION && bits == S::BITS,
+            concat!(
+                "The sketches were fitted with precision {} and {} bits, ",
+                "but are loaded with precision {} and {} bits."
+            ),
+            precision,
+            bits,
+            S::PRECISION,
+            S::BITS
+        );
+
         if check_graph_hash {
             if let Some(graph_hash) = data.as_ref().graph_hash {
-                let mut hasher = std::collections::hash_map::DefaultHasher::new();
-                graph.hash(&mut hasher);
+                let provided_graph_hash = hash_graph(&graph);
                 ensure!(
-                    graph_hash == hasher.finish(),
-                    "The graph hash does not match the provided graph."
+                    graph_hash == provided_graph_hash,
+                    concat!(
+                        "The sketches were fitted on a graph with hash {:#018x}, ",
+                        "but the provided graph has hash {:#018x}: ",
+                        "the sketches cannot be reused on a different graph."
+                    ),
+                    graph_hash,
+                    provided_graph_hash
                 );
             }
         }
@@ -102,24 +415,22 @@ where
         Ok(HyperSketching {
             data,
             graph,
-            masks: None,
             _marker: PhantomData,
         })
     }
 }
 
-impl<G, Counters, P, const BITS: usize>
-    HyperSketching<G, HyperSketchingData<Counters>, Counters, P, BITS>
+impl<G, Counters, S> HyperSketching<G, HyperSketchingData<Counters>, Counters, S>
 where
-    G: Graph + Hash,
-    Counters: SequenceAllocable<Item = HyperLogLog<P, BITS>> + Deserialize + TypeHash,
-    P: Precision + WordType<BITS>,
+    G: Successors,
+    Counters: SequenceAllocable<Item = S> + Deserialize + TypeHash,
+    S: SetSketch,
 {
     pub unsafe fn load<PP>(
         path: PP,
         graph: G,
         check_graph_hash: bool,
-    ) -> Result<HyperSketching<G, HyperSketchingData<Counters>, Counters, P, BITS>>
+    ) -> Result<HyperSketching<G, HyperSketchingData<Counters>, Counters, S>>
     where
         PP: AsRef<Path>,
     {
@@ -133,22 +444,47 @@ where
     }
 }
 
-type DeserType<Counters> =
+impl<G, Data, Counters, S> HyperSketching<G, Data, Counters, S>
+where
+    Counters: Sequence<Item = S>,
+    S: SetSketch,
+    Data: AsRef<HyperSketchingData<Counters>>,
+    HyperSketchingData<Counters>: Serialize,
+{
+    /// Stores the sketches, the parameters and the hash of the graph they
+    /// were fitted on, so that they can be loaded or memory-mapped back.
+    ///
+    /// # Arguments
+    /// * `path`: PP - The path of the file to store the data into.
+    pub fn store<PP>(&self, path: PP) -> Result<()>
+    where
+        PP: AsRef<Path>,
+    {
+        self.data.as_ref().store(path.as_ref()).with_context(|| {
+            format!(
+                "Error while storing hypersketching data to {}",
+                path.as_ref().display()
+            )
+        })?;
+        Ok(())
+    }
+}
+
+pub(crate) type DeserType<Counters> =
     MemCase<<HyperSketchingData<Counters> as DeserializeInner>::DeserType<'static>>;
 
-impl<G, Counters, P, const BITS: usize>
-    HyperSketching<G, HyperSketchingData<Counters>, Counters, P, BITS>
+impl<G, Counters, S> HyperSketching<G, HyperSketchingData<Counters>, Counters, S>
 where
-    G: Graph + Hash,
-    Counters: SequenceAllocable<Item = HyperLogLog<P, BITS>> + Deserialize + TypeHash,
-    P: Precision + WordType<BITS>,
+    G: Successors,
+    Counters: SequenceAllocable<Item = S> + Deserialize + TypeHash,
+    S: SetSketch,
     DeserType<Counters>: AsRef<HyperSketchingData<Counters>>,
 {
     pub unsafe fn mmap<PP>(
         path: PP,
         graph: G,
         check_graph_hash: bool,
-    ) -> Result<HyperSketching<G, DeserType<Counters>, Counters, P, BITS>>
+    ) -> Result<HyperSketching<G, DeserType<Counters>, Counters, S>>
     where
         PP: AsRef<Path>,
     {
@@ -164,20 +500,13 @@ where
         })?;
         Self::from_data(data, graph, check_graph_hash)
     }
-
-    /*
-    ParReplica::new(BitFieldVec::new(
-                ,
-                graph.number_of_nodes(),
-            ))
-    */
 }
 
-impl<G, Data, Counters, P, const BITS: usize> HyperSketching<G, Data, Counters, P, BITS>
+impl<G, Data, Counters, S> HyperSketching<G, Data, Counters, S>
 where
     G: Graph,
-    Counters: Sequence<Item = HyperLogLog<P, BITS>>,
-    P: Precision + WordType<BITS>,
+    Counters: Sequence<Item = S>,
+    S: SetSketch,
     Data: AsRef<HyperSketchingData<Counters>>,
 {
     /// Returns the number of hops.
@@ -209,17 +538,73 @@ where
         self.not_visited() - 1
     }
 
+    /// Returns a mask to compute the bias-aware features, with
+    /// `bits_per_node` bits for each node of the graph.
+    fn new_mask(&self) -> BitFieldVec {
+        BitFieldVec::new(self.bits_per_node(), self.graph.number_of_nodes())
+    }
+
+    /// Returns the precision of the sketches, e.g. the base-2 logarithm of
+    /// the number of registers of HyperLogLog counters.
+    #[inline(always)]
+    pub fn precision(&self) -> usize {
+        self.data.as_ref().precision
+    }
+
+    /// Returns the number of bits of each register of the sketches.
+    #[inline(always)]
+    pub fn bits(&self) -> usize {
+        self.data.as_ref().bits
+    }
+
+    /// Returns the hash of the graph the sketches were fitted on, if any.
+    ///
+    /// The hash only depends on the edges of the graph, and is the same
+    /// across platforms and releases of Rust, so that the stored sketches
+    /// can be loaded back anywhere.
+    #[inline(always)]
+    pub fn graph_hash(&self) -> Option<u64> {
+        self.data.as_ref().graph_hash
+    }
+
     #[inline(always)]
     pub fn normalize(&self) -> bool {
         self.data.as_ref().normalize
     }
+
+    /// Returns the hops whose counters are retained, sorted and ending with
+    /// the last hop. By default, all the hops are retained.
+    #[inline(always)]
+    pub fn retained_hops(&self) -> &[usize] {
+        &self.data.as_ref().retained_hops
+    }
+
+    /// Returns the layout of the features of each edge, computed on the
+    /// retained hops, to name and slice their columns.
+    #[inline(always)]
+    pub fn feature_layout(&self) -> FeatureLayout {
+        FeatureLayout::new(self.retained_hops())
+    }
+
+    /// Returns the number of features of each edge, i.e. the overlap matrix
+    /// followed by the left and right differences of each retained hop.
+    #[inline(always)]
+    pub fn feature_len(&self) -> usize {
+        self.feature_layout().len()
+    }
+
+    /// Returns the names of the features of each edge, in the order in which
+    /// they are written.
+    pub fn feature_names(&self) -> Vec<String> {
+        self.feature_layout().names()
+    }
 }
 
-impl<G, Data, Counters, P, const BITS: usize> HyperSketching<G, Data, Counters, P, BITS>
+impl<G, Data, Counters, S> HyperSketching<G, Data, Counters, S>
 where
     G: Graph,
-    Counters: Sequence<Item = HyperLogLog<P, BITS>>,
-    P: Precision + WordType<BITS>,
+    Counters: Sequence<Item = S>,
+    S: SetSketch,
     Data: AsMut<HyperSketchingData<Counters>>,
 {
     #[inline(always)]
@@ -228,70 +613,186 @@ where
     }
 }
 
-impl<G, Data, Counters, P, const BITS: usize> HyperSketching<G, Data, Counters, P, BITS>
+impl<G, Data, Counters, S> HyperSketching<G, Data, Counters, S>
+where
+    G: Graph,
+    Counters: SequenceAllocable<Item = S>,
+    S: SetSketch,
+    Data: AsMut<HyperSketchingData<Counters>>,
+{
+    /// Sets the hops whose counters are retained by the next fit, and drops
+    /// the current counters, which must be fitted again.
+    ///
+    /// The counters of the other hops are only kept while fitting the next
+    /// hop, in two rolling buffers: the counters of the next retained hop and
+    /// a spare counter for each node. Fitting thus allocates one counter more
+    /// than the retained hops for each node, instead of one for each hop, e.g.
+    /// two counters for each node when only the last hop is retained. The
+    /// features are then computed on the balls of the retained hops: the
+    /// features of a retained hop cover the nodes of its ball which are not
+    /// in the ball of the previous retained hop.
+    ///
+    /// # Arguments
+    /// * `retained_hops`: &[usize] - The hops to retain, strictly increasing and ending with the last hop.
+    pub fn set_retained_hops(&mut self, retained_hops: &[usize]) -> Result<()> {
+        let data = self.data.as_mut();
+        ensure!(
+            retained_hops.last() == Some(&(data.number_of_hops - 1)),
+            "The retained hops must end with the last hop {}, but are {:?}.",
+            data.number_of_hops - 1,
+            retained_hops
+        );
+        ensure!(
+            retained_hops.windows(2).all(|pair| pair[0] < pair[1]),
+            "The retained hops must be strictly increasing, but are {:?}.",
+            retained_hops
+        );
+        data.retained_hops = retained_hops.to_vec();
+        data.counters = Counters::empty();
+        data.graph_hash = None;
+        Ok(())
+    }
+}
+
+impl<G, Data, Counters, S> HyperSketching<G, Data, Counters, S>
 where
     G: Successors + Send + Sync,
     <G as Graph>::Nodes: IntoIndexedParallelIterator<Item = G::Node>,
-    Counters: Send
-        + Sync
-        + SequenceAllocable<Item = HyperLogLog<P, BITS>>
-        + AsRef<[HyperLogLog<P, BITS>]>
-        + AsMut<[HyperLogLog<P, BITS>]>,
-    P: Precision + WordType<BITS>,
+    Counters: Send + Sync + SequenceAllocable<Item = S> + AsRef<[S]> + AsMut<[S]>,
+    S: SetSketch,
     Data: AsRef<HyperSketchingData<Counters>> + AsMut<HyperSketchingData<Counters>>,
 {
     /// Fit the HyperBall model to the provided support.
+    ///
+    /// The hash of the support is recorded, so that the fitted model can
+    /// only be loaded back with the same graph.
     pub fn fit(&mut self) {
         let number_of_hops = self.number_of_hops();
-        let number_of_counters = self.graph.number_of_nodes() * number_of_hops;
+        let number_of_counters = self.graph.number_of_nodes() * self.retained_hops().len();
+        let graph_hash = hash_graph(&self.graph);
         let data = self.data.as_mut();
+        data.graph_hash = Some(graph_hash);
         data.counters = Counters::defaulted(number_of_counters);
+        fit_counters(
+            &self.graph,
+            data.counters.as_mut(),
+            number_of_hops,
+            &data.retained_hops,
+        );
+    }
 
-        // Create HyperLogLog counters for all nodes in the graph
-        self.graph
-            .nodes()
-            .into_par_iter()
-            .zip(
-                data.counters
-                    .as_mut()
-                    .par_chunks_exact_mut(number_of_hops)
-                    .map(|counters| &mut counters[0]),
-            )
-            .for_each(|(node, hll): (G::Node, &mut HyperLogLog<P, BITS>)| {
-                hll.insert(node);
-                hll.bitor_assign(self.graph.successors(node));
-            });
+    /// Updates the sketches after the insertion of new edges, replacing the
+    /// support with the updated graph, whose hash is recorded.
+    ///
+    /// Only the counters of the nodes whose balls may have grown are
+    /// recomputed, hop by hop: as the union of the counters is monotone, the
+    /// sketches are the same as the ones fitted on the updated graph.
+    /// The nodes of the previous graph must keep their identifiers, and the
+    /// new nodes, if any, must follow them. The counters of all the hops
+    /// must be retained, as each hop is updated from the previous one.
+    ///
+    /// # Arguments
+    /// * `graph`: G - The updated graph, with the edges of the previous one and the new edges.
+    /// * `edges`: &[(G::Node, G::Node)] - The new edges.
+    ///
+    /// # Errors
+    /// Before the model is modified, if the model is not fitted with all the
+    /// hops retained, or if the updated graph is not the previous one with
+    /// the new edges: the previous graph must be a subgraph of the updated
+    /// one, whose other edges must be new edges or, when the graph is
+    /// undirected, their reverse. A directed graph, e.g. a CSR, lists both
+    /// directions of a symmetric edge among the new edges. Checking the
+    /// graphs visits both of them once.
+    pub fn insert_edges(&mut self, graph: G, edges: &[(G::Node, G::Node)]) -> Result<()> {
+        let number_of_hops = self.number_of_hops();
+        let previous_number_of_nodes = self.graph.number_of_nodes();
+        let number_of_nodes = graph.number_of_nodes();
+        ensure!(
+            self.retained_hops().len() == number_of_hops,
+            "Inserting edges requires the counters of all the hops to be retained."
+        );
+        ensure!(
+            self.data.as_ref().counters.as_ref().len() == previous_number_of_nodes * number_of_hops,
+            "The model must be fitted before inserting edges."
+        );
+        ensure!(
+            number_of_nodes >= previous_number_of_nodes,
+            "The updated graph has {} nodes, fewer than the {} nodes of the previous graph.",
+            number_of_nodes,
+            previous_number_of_nodes
+        );
+        check_inserted_edges(&self.graph, &graph, edges)?;
+
+        // The new nodes get their counters after the ones of the previous nodes.
+        if number_of_nodes > previous_number_of_nodes {
+            let mut counters = Counters::defaulted(number_of_nodes * number_of_hops);
+            counters.as_mut()[..previous_number_of_nodes * number_of_hops]
+                .clone_from_slice(self.data.as_ref().counters.as_ref());
+            self.data.as_mut().counters = counters;
+        }
+        self.graph = graph;
+        let graph_hash = hash_graph(&self.graph);
+        self.data.as_mut().graph_hash = Some(graph_hash);
+
+        // The nodes whose ball may have grown at the current hop, starting
+        // from the new nodes and the endpoints of the new edges.
+        let mut dirty = vec![false; number_of_nodes];
+        dirty[previous_number_of_nodes..].fill(true);
+        for &(src, dst) in edges {
+            let (src, dst): (usize, usize) = (src.to(), dst.to());
+            dirty[src] = true;
+            dirty[dst] = true;
+        }
 
-        // Iterate over all hops and update the counters accordingly
-        (1..number_of_hops).for_each(|k| {
-            self.graph
+        for k in 0..number_of_hops {
+            if k > 0 {
+                // The ball of a node grows when the ball of one of its
+                // successors grew at the previous hop.
+                dirty = self
+                    .graph
+                    .nodes()
+                    .into_par_iter()
+                    .zip(dirty.par_iter())
+                    .map(|(node, &is_dirty)| {
+                        is_dirty
+                            || self.graph.successors(node).into_iter().any(|dst| {
+                                let dst: usize = dst.to();
+                                dirty[dst]
+                            })
+                    })
+                    .collect();
+            }
+
+            let counters = self.data.as_ref().counters.as_ref();
+            let updated: Vec<(usize, S)> = self
+                .graph
                 .nodes()
                 .into_par_iter()
-                .zip(
-                    data.counters
-                        .as_ref()
-                        .par_chunks_exact(number_of_hops)
-                        .map(|row| {
-                            let (previous, current) = row.split_at(k);
-                            let current = unsafe {
-                                core::mem::transmute_copy::<_, &mut [HyperLogLog<P, BITS>]>(
-                                    &current,
-                                )
-                            };
-                            (&previous[k - 1], &mut current[0])
-                        }),
-                )
-                .for_each(|(node, (previous_counter, current_counter))| {
-                    // Iterate over all neighbors of the current node
-                    *current_counter = self
-                        .graph
-                        .successors(node)
-                        .into_iter()
-                        .map(|dst| data.counters.as_ref()[dst.to() * number_of_hops + k - 1])
-                        .union()
-                        | previous_counter;
-                });
-        });
+                .zip(dirty.par_iter())
+                .filter(|&(_, &is_dirty)| is_dirty)
+                .map(|(node, _)| {
+                    let node_index: usize = node.to();
+                    let counter = if k == 0 {
+                        first_hop_sketch(&self.graph, node)
+                    } else {
+                        next_hop_sketch(
+                            &self.graph,
+                            node,
+                            &counters[node_index * number_of_hops + k - 1],
+                            |successor| &counters[successor * number_of_hops + k - 1],
+                        )
+                    };
+                    (node_index, counter)
+                })
+                .collect();
+
+            let counters = self.data.as_mut().counters.as_mut();
+            for (node, counter) in updated {
+                counters[node * number_of_hops + k] = counter;
+            }
+        }
+
+        Ok(())
     }
 }
 
@@ -381,76 +882,91 @@ impl Normalizer for NotNormalized {
     }
 }
 
-impl<G, Data, Counters, P, const BITS: usize> HyperSketching<G, Data, Counters, P, BITS>
+impl<G, Data, Counters, S> HyperSketching<G, Data, Counters, S>
 where
-    G: Successors + Send + Sync,
-    <G as Graph>::Nodes: IntoIndexedParallelIterator<Item = G::Node>,
-    Counters: Send
-        + Sync
-        + SequenceAllocable<Item = HyperLogLog<P, BITS>>
-        + AsRef<[HyperLogLog<P, BITS>]>
-        + AsMut<[HyperLogLog<P, BITS>]>,
-    P: Precision + WordType<BITS>,
-    Data: AsRef<HyperSketchingData<Counters>> + AsMut<HyperSketchingData<Counters>>,
+    G: Successors,
+    Counters: Sequence<Item = S> + AsRef<[S]>,
+    S: SetSketch,
+    Data: AsRef<HyperSketchingData<Counters>>,
 {
+    /// Returns the extractor of the biased edge features, to compute them
+    /// for batches of edges.
+    #[inline(always)]
+    pub fn biased_features<const INSERT_EDGE: bool>(
+        &self,
+    ) -> BiasedEdgeFeatures<'_, G, Data, Counters, S, INSERT_EDGE> {
+        BiasedEdgeFeatures { sketching: self }
+    }
+
+    /// Returns the extractor of the bias-aware edge features, to compute
+    /// them for batches of edges.
+    #[inline(always)]
+    pub fn bias_aware_features<const INSERT_EDGE: bool>(
+        &self,
+    ) -> BiasAwareEdgeFeatures<'_, G, Data, Counters, S, INSERT_EDGE> {
+        BiasAwareEdgeFeatures { sketching: self }
+    }
 
     #[inline(always)]
     pub fn biased_edge_features<const INSERT_EDGE: bool>(
+        &self,
+        edge: (G::Node, G::Node),
+        target: &mut [f32],
+    ) {
+        self.biased_edge_features_dispatched(edge, self.normalize(), target)
+    }
+
+    /// Writes the biased features of the edge, normalized or not
+    /// regardless of the normalization of the model.
+    #[inline(always)]
+    fn biased_edge_features_dispatched(
         &self,
         (src, dst): (G::Node, G::Node),
+        normalize: bool,
         target: &mut [f32],
     ) {
         // We get the usize representation of the nodes.
         let src: usize = src.to();
         let dst: usize = dst.to();
 
-        // Now, we can compute the overlap matrix.
-        if self.normalize() {
-            let (overlaps, left_diffs, right_diffs) = <HyperLogLog<P, BITS> as HyperSpheresSketch<f32>>::normalized_overlap_and_differences_cardinality_matrices_vec(
-                &self.data.as_ref().counters.as_ref()[src * self.number_of_hops()..(src + 1) * self.number_of_hops()],
-                &self.data.as_ref().counters.as_ref()[dst * self.number_of_hops()..(dst + 1) * self.number_of_hops()],
-            );
-            target
-            .iter_mut()
-            .zip(
-                overlaps
-                    .into_iter()
-                    .flat_map(|o| o)
-                    .chain(left_diffs)
-                    .chain(right_diffs),
-            )
-            .for_each(|(t, v)| *t = v);
-        } else {
-            let (overlaps, left_diffs, right_diffs) = <HyperLogLog<P, BITS> as HyperSpheresSketch<f32>>::overlap_and_differences_cardinality_matrices_vec(
-                &self.data.as_ref().counters.as_ref()[src * self.number_of_hops()..(src + 1) * self.number_of_hops()],
-                &self.data.as_ref().counters.as_ref()[dst * self.number_of_hops()..(dst + 1) * self.number_of_hops()],
-            );
-            target
-            .iter_mut()
-            .zip(
-                overlaps
-                    .into_iter()
-                    .flat_map(|o| o)
-                    .chain(left_diffs)
-                    .chain(right_diffs),
-            )
-            // 
-            .for_each(|(t, v)| *t = v.round());
-        };
+        let hops = self.retained_hops().len();
+        let counters = self.data.as_ref().counters.as_ref();
+        S::overlaps_and_differences(
+            &counters[src * hops..(src + 1) * hops],
+            &counters[dst * hops..(dst + 1) * hops],
+            normalize,
+            target,
+        );
     }
 
+    /// Writes the bias-aware features of the edge into the zeroed target.
+    ///
+    /// The visit is masked with a mask allocated for the call: to compute
+    /// the features of many edges, use [`bias_aware_features`](Self::bias_aware_features),
+    /// which allocates a mask for each rayon task.
     #[inline(always)]
     pub fn bias_aware_edge_features<const INSERT_EDGE: bool>(
         &self,
-        (src, dst): (G::Node, G::Node),
+        edge: (G::Node, G::Node),
+        target: &mut [f32],
+    ) {
+        self.bias_aware_edge_features_with_mask::<INSERT_EDGE>(edge, &mut self.new_mask(), target)
+    }
+
+    /// Writes the bias-aware features of the edge into the zeroed target,
+    /// masking the visit with the provided mask, which is reset first.
+    #[inline(always)]
+    fn bias_aware_edge_features_with_mask<const INSERT_EDGE: bool>(
+        &self,
+        edge: (G::Node, G::Node),
+        mask: &mut BitFieldVec,
         target: &mut [f32],
     ) {
         if self.data.as_ref().normalize {
-            self.bias_aware_edge_features_dispatched::<INSERT_EDGE, Normalized>((src, dst), target)
+            self.bias_aware_edge_features_dispatched::<INSERT_EDGE, Normalized>(edge, mask, target)
         } else {
             self.bias_aware_edge_features_dispatched::<INSERT_EDGE, NotNormalized>(
-                (src, dst),
-                target,
+                edge, mask, target,
             )
         }
     }
@@ -458,18 +974,23 @@ where
     fn bias_aware_edge_features_dispatched<const INSERT_EDGE: bool, Norm: Normalizer>(
         &self,
         (src, dst): (G::Node, G::Node),
+        mask: &mut BitFieldVec,
         target: &mut [f32],
     ) {
-        let mask = unsafe {
-            self.masks
-                .as_ref()
-                .expect("bias_aware_edge_features called before fitting")
-                .get_mut()
-        };
         mask.reset_ones();
 
         let hops = self.number_of_hops();
-        let mut normalizer = Norm::new(hops);
+        let retained_hops = self.retained_hops();
+        // The features of each hop are accumulated in the ones of the ball
+        // of the retained hop it belongs to.
+        let retained = |hop: usize| -> usize {
+            if retained_hops.len() == hops {
+                hop
+            } else {
+                retained_hop_index(retained_hops, hop)
+            }
+        };
+        let mut normalizer = Norm::new(retained_hops.len());
 
         // We check that the provided target features are all zero.
         debug_assert!(
@@ -477,15 +998,20 @@ where
             "The provided target features must be all zero."
         );
 
-        let (differential_intersections, differences) = target.split_at_mut(hops * hops);
+        let feature_hops = retained_hops.len();
+        let (differential_intersections, differences) =
+            target.split_at_mut(feature_hops * feature_hops);
 
-        debug_assert_eq!(differential_intersections.len(), hops * hops);
+        debug_assert_eq!(
+            differential_intersections.len(),
+            feature_hops * feature_hops
+        );
 
-        let (left_difference, right_difference) = differences.split_at_mut(hops);
+        let (left_difference, right_difference) = differences.split_at_mut(feature_hops);
 
-        debug_assert_eq!(left_difference.len(), hops);
+        debug_assert_eq!(left_difference.len(), feature_hops);
 
-        debug_assert_eq!(right_difference.len(), hops);
+        debug_assert_eq!(right_difference.len(), feature_hops);
 
         // First, we work on the source node.
 
@@ -502,8 +1028,8 @@ where
             let w_hops: usize = unsafe { mask.get_unchecked(w_usize) };
             let not_visited = w_hops == self.not_visited();
 
-            left_difference[i] += not_visited as usize as f32;
-            normalizer.inc_left_cardinality(i, not_visited as usize as f32);
+            left_difference[retained(i)] += not_visited as usize as f32;
+            normalizer.inc_left_cardinality(retained(i), not_visited as usize as f32);
             unsafe {
                 mask.set_unchecked(w_usize, core::cmp::min(w_hops, i));
             }
@@ -549,11 +1075,11 @@ where
             let not_deleted = w_hops != self.deleted();
 
             if !not_visited && not_deleted {
-                differential_intersections[w_hops * hops + i] += 1.0;
-                left_difference[w_hops] -= 1.0;
+                differential_intersections[retained(w_hops) * feature_hops + retained(i)] += 1.0;
+                left_difference[retained(w_hops)] -= 1.0;
             }
-            right_difference[i] += not_visited as usize as f32;
-            normalizer.inc_right_cardinality(i, not_deleted as usize as f32);
+            right_difference[retained(i)] += not_visited as usize as f32;
+            normalizer.inc_right_cardinality(retained(i), not_deleted as usize as f32);
 
             unsafe {
                 mask.set_unchecked(w_usize, self.deleted());
@@ -605,3 +1131,165 @@ where
         );
     }
 }
+
+impl<G, Data, Counters, S> HyperSketching<G, Data, Counters, S>
+where
+    G: Graph,
+    Counters: Sequence<Item = S> + AsRef<[S]> + Sync,
+    S: SetSketch,
+    Data: AsRef<HyperSketchingData<Counters>>,
+{
+    /// Returns the number of features of each node, i.e. one for each retained hop.
+    #[inline(always)]
+    pub fn node_feature_len(&self) -> usize {
+        self.retained_hops().len()
+    }
+
+    /// Returns the names of the features of each node, in the order in
+    /// which they are written.
+    pub fn node_feature_names(&self) -> Vec<String> {
+        self.retained_hops()
+            .iter()
+            .map(|i| format!("cardinality_h{}", i))
+            .collect()
+    }
+
+    /// Writes the estimated cardinalities of the balls of each node into the
+    /// rows of the target matrix, in parallel.
+    ///
+    /// The ball of the hop `i` of a node holds the nodes within `i + 1` hops
+    /// from it, the node included, and the cardinalities are not normalized.
+    ///
+    /// # Arguments
+    /// * `target`: &mut [f32] - The row-major matrix of shape `nodes × retained hops`.
+    ///
+    /// # Panics
+    /// If the model is not fitted or the target matrix does not have the expected length.
+    pub fn node_features(&self, target: &mut [f32]) {
+        let hops = self.node_feature_len();
+        let counters = self.data.as_ref().counters.as_ref();
+        assert_eq!(
+            counters.len(),
+            self.graph.number_of_nodes() * hops,
+            "The model must be fitted before computing the node features."
+        );
+        assert_eq!(
+            target.len(),
+            counters.len(),
+            "The target matrix must have shape nodes × retained hops."
+        );
+        target
+            .par_iter_mut()
+            .zip(counters.par_iter())
+            .for_each(|(feature, counter)| *feature = counter.cardinality());
+    }
+
+    /// Returns the row-major matrix of shape `nodes × retained hops` with the
+    /// estimated cardinalities of the balls of each node.
+    pub fn node_features_matrix(&self) -> Vec<f32> {
+        let mut target = vec![0.0; self.graph.number_of_nodes() * self.node_feature_len()];
+        self.node_features(&mut target);
+        target
+    }
+}
+
+/// The biased edge features of a HyperSketching, estimated from the counters alone.
+pub struct BiasedEdgeFeatures<'a, G, Data, Counters, S, const INSERT_EDGE: bool>
+where
+    S: SetSketch,
+    Counters: Sequence<Item = S>,
+{
+    sketching: &'a HyperSketching<G, Data, Counters, S>,
+}
+
+/// The bias-aware edge features of a HyperSketching, computed with a
+/// breadth-first visit that skips the edge itself.
+pub struct BiasAwareEdgeFeatures<'a, G, Data, Counters, S, const INSERT_EDGE: bool>
+where
+    S: SetSketch,
+    Counters: Sequence<Item = S>,
+{
+    sketching: &'a HyperSketching<G, Data, Counters, S>,
+}
+
+impl<G, Data, Counters, S, const INSERT_EDGE: bool> EdgeFeatures
+    for BiasedEdgeFeatures<'_, G, Data, Counters, S, INSERT_EDGE>
+where
+    G: Successors + Sync,
+    Counters: Sequence<Item = S> + AsRef<[S]> + Sync,
+    S: SetSketch,
+    Data: AsRef<HyperSketchingData<Counters>> + Sync,
+{
+    type Node = G::Node;
+
+    #[inline(always)]
+    fn feature_len(&self) -> usize {
+        self.sketching.feature_len()
+    }
+
+    #[inline(always)]
+    fn feature_names(&self) -> Vec<String> {
+        self.sketching.feature_names()
+    }
+
+    #[inline(always)]
+    fn edge_features(&self, edge: (G::Node, G::Node), target: &mut [f32]) {
+        self.sketching.biased_edge_features::<INSERT_EDGE>(edge, target)
+    }
+}
+
+impl<G, Data, Counters, S, const INSERT_EDGE: bool> EdgeFeatures
+    for BiasAwareEdgeFeatures<'_, G, Data, Counters, S, INSERT_EDGE>
+where
+    G: Successors + Sync,
+    Counters: Sequence<Item = S> + AsRef<[S]> + Sync,
+    S: SetSketch,
+    Data: AsRef<HyperSketchingData<Counters>> + Sync,
+{
+    type Node = G::Node;
+
+    #[inline(always)]
+    fn feature_len(&self) -> usize {
+        self.sketching.feature_len()
+    }
+
+    #[inline(always)]
+    fn feature_names(&self) -> Vec<String> {
+        self.sketching.feature_names()
+    }
+
+    /// Writes the features of the edge, allocating the mask of its visit.
+    #[inline(always)]
+    fn edge_features(&self, edge: (G::Node, G::Node), target: &mut [f32]) {
+        self.sketching.bias_aware_edge_features::<INSERT_EDGE>(edge, target)
+    }
+
+    /// Writes the features of the edges in parallel, allocating the mask of
+    /// the visits once for each rayon task instead of once for each edge.
+    fn par_edge_features<I>(&self, edges: I, target: &mut [f32])
+    where
+        I: IntoParallelIterator<Item = (G::Node, G::Node)>,
+        I::Iter: IndexedParallelIterator,
+    {
+        let edges = edges.into_par_iter();
+        let feature_len = self.feature_len();
+        assert_eq!(
+            target.len(),
+            edges.len() * feature_len,
+            "The target matrix must have shape edges × feature length."
+        );
+        if feature_len == 0 {
+            return;
+        }
+        edges
+            .zip(target.par_chunks_exact_mut(feature_len))
+            .for_each_init(
+                || self.sketching.new_mask(),
+                |mask, (edge, row)| {
+                    row.fill(0.0);
+                    self.sketching
+                        .bias_aware_edge_features_with_mask::<INSERT_EDGE>(edge, mask, row);
+                },
+            );
+    }
+}
@@ -0,0 +1,145 @@
+//! Module providing the out-of-core fit of the sketches into a file, for the
+//! graphs whose counters do not fit in memory.
+//!
+//! The counters are written into a memory-mapped file laid out as the data
+//! stored by [`HyperSketching::store`], so that the file can be opened
+//! directly with [`HyperSketching::mmap`] or loaded with [`HyperSketching::load`].
+//! As the hop `k` of the nodes only reads the hop `k - 1` of their successors,
+//! only the pages of the counters being read and written need to reside in
+//! memory, and the operating system writes the others back to the file.
+//!
+//! # Layout
+//! epserde stores the fields of the data in order, and the counters, which
+//! are its last field, as a zero-copy vector: its length as a usize, the zero
+//! padding aligning the counters in the file, and the bytes of the counters.
+//! The file is thus the data stored with no counters, whose length is set to
+//! the number of counters, followed by the counters. The position of the
+//! length is read from the schema that epserde returns when serializing.
+use crate::hashing::hash_graph;
+use crate::sketches::SetSketch;
+use crate::{fit_counters, HyperSketching, HyperSketchingData};
+use anyhow::{ensure, Context, Result};
+use core::mem::{align_of, size_of};
+use epserde::{ser::Serialize, traits::ZeroCopy};
+use graph::{Graph, Successors};
+use irontraits::{IntoIndexedParallelIterator, Sequence};
+use memmap2::MmapMut;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+/// Returns the bytes of the stored data preceding its counters, which are its
+/// last field, with the provided number of counters.
+///
+/// The data is serialized by epserde with no counters, and the length of the
+/// counters, whose position is given by the schema of the serialization, is
+/// then set to the provided number of counters.
+fn header<S>(
+    number_of_hops: usize,
+    retained_hops: &[usize],
+    normalize: bool,
+    graph_hash: u64,
+    number_of_counters: usize,
+) -> Result<Vec<u8>>
+where
+    S: SetSketch + ZeroCopy,
+    HyperSketchingData<Vec<S>>: Serialize,
+{
+    let data = HyperSketchingData::<Vec<S>> {
+        normalize,
+        number_of_hops,
+        graph_hash: Some(graph_hash),
+        precision: S::PRECISION,
+        bits: S::BITS,
+        retained_hops: retained_hops.to_vec(),
+        counters: Vec::new(),
+    };
+    let mut header = Vec::new();
+    let schema = data.serialize_with_schema(&mut header)?;
+
+    let length = schema
+        .0
+        .iter()
+        .find(|row| row.field.ends_with("counters.len"))
+        .context("The schema of the stored data has no length of the counters.")?;
+    ensure!(
+        length.size == size_of::<usize>() && length.offset + length.size <= header.len(),
+        "The length of the counters in the schema of the stored data is not a usize."
+    );
+    // As any usize stored by epserde, the length is in native byte order.
+    header[length.offset..length.offset + length.size]
+        .copy_from_slice(&number_of_counters.to_ne_bytes());
+    Ok(header)
+}
+
+impl<G, Data, Counters, S> HyperSketching<G, Data, Counters, S>
+where
+    G: Successors + Send + Sync,
+    <G as Graph>::Nodes: IntoIndexedParallelIterator<Item = G::Node>,
+    Counters: Sequence<Item = S>,
+    S: SetSketch + ZeroCopy,
+    Data: AsRef<HyperSketchingData<Counters>>,
+    HyperSketchingData<Vec<S>>: Serialize,
+{
+    /// Fits the sketches of the support into the file at the provided path,
+    /// with the hops and the normalization of the model.
+    ///
+    /// The file holds the same data that [`HyperSketching::store`] writes
+    /// after [`HyperSketching::fit`], hash of the graph included, and is to
+    /// be opened with [`HyperSketching::mmap`]. The model itself is left as is.
+    /// When only some of the hops are retained, the spare counter of each
+    /// node used to fit the other hops is kept in memory.
+    ///
+    /// # Arguments
+    /// * `path`: PP - The path of the file to fit the sketches into, which is overwritten.
+    pub fn fit_to_file<PP>(&self, path: PP) -> Result<()>
+    where
+        PP: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let context = || {
+            format!(
+                "Error while fitting hypersketching data into {}",
+                path.display()
+            )
+        };
+        let number_of_hops = self.number_of_hops();
+        let retained_hops = self.retained_hops();
+        let number_of_counters = self.graph.number_of_nodes() * retained_hops.len();
+        let header = header::<S>(
+            number_of_hops,
+            retained_hops,
+            self.normalize(),
+            hash_graph(&self.graph),
+            number_of_counters,
+        )
+        .with_context(context)?;
+
+        let mut file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)
+            .with_context(context)?;
+        file.write_all(&header).with_context(context)?;
+        file.set_len((header.len() + number_of_counters * size_of::<S>()) as u64)
+            .with_context(context)?;
+
+        let mut mmap = unsafe { MmapMut::map_mut(&file) }.with_context(context)?;
+        let counters = mmap[header.len()..].as_mut_ptr();
+        ensure!(
+            counters as usize % align_of::<S>() == 0,
+            "The counters in {} are not aligned.",
+            path.display()
+        );
+        // SAFETY: the counters are zero-copy, aligned and within the file, and
+        // every counter is written before being read.
+        let counters =
+            unsafe { core::slice::from_raw_parts_mut(counters as *mut S, number_of_counters) };
+        fit_counters(&self.graph, counters, number_of_hops, retained_hops);
+
+        mmap.flush().with_context(context)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,386 @@
+//! Module providing the set sketches HyperSketching can use as counters.
+//!
+//! Besides the HyperLogLog counters, the crate provides a MinHash and a
+//! bottom-k sketch, whose intersection estimates are more accurate for
+//! Jaccard-style features at the price of more bits per register.
+use crate::hashing::{splitmix64, StableHasher};
+use crate::{Normalized, Normalizer};
+use core::hash::{Hash, Hasher};
+use epserde::Epserde;
+use hyperloglog_rs::prelude::*;
+
+/// Trait for the sketches of sets of nodes used as counters of the hops.
+pub trait SetSketch: Clone + Default + Send + Sync {
+    /// The precision of the sketch, e.g. the base-2 logarithm of the number
+    /// of registers of HyperLogLog counters, or the number of hashes kept.
+    const PRECISION: usize;
+    /// The number of bits of each register of the sketch.
+    const BITS: usize;
+
+    /// Inserts the element in the sketched set.
+    fn insert_element<T: Hash>(&mut self, element: T);
+
+    /// Merges the provided sketch into this one, which then sketches the
+    /// union of the two sets.
+    fn union_assign(&mut self, other: &Self);
+
+    /// Returns the estimated cardinality of the sketched set.
+    fn cardinality(&self) -> f32;
+
+    /// Returns the estimated cardinality of the intersection of the sets,
+    /// by default with the inclusion-exclusion principle.
+    fn intersection_cardinality(&self, other: &Self) -> f32 {
+        let mut union = self.clone();
+        union.union_assign(other);
+        (self.cardinality() + other.cardinality() - union.cardinality()).max(0.0)
+    }
+
+    /// Writes the overlap matrix of the hops of the two nodes followed by
+    /// their left and right differences into the zeroed target.
+    ///
+    /// The default implementation derives the overlaps of the hops from the
+    /// estimated intersections of the balls with the inclusion-exclusion
+    /// principle, and normalizes them as the bias-aware features.
+    ///
+    /// # Arguments
+    /// * `left`: &[Self] - The sketches of the balls of the source, one for each hop.
+    /// * `right`: &[Self] - The sketches of the balls of the destination, one for each hop.
+    /// * `normalize`: bool - Whether to normalize the cardinalities.
+    /// * `target`: &mut [f32] - The zeroed row of the features.
+    fn overlaps_and_differences(
+        left: &[Self],
+        right: &[Self],
+        normalize: bool,
+        target: &mut [f32],
+    ) {
+        let hops = left.len();
+        let (overlaps, differences) = target.split_at_mut(hops * hops);
+        let (left_difference, right_difference) = differences.split_at_mut(hops);
+
+        // The intersections of the balls, where the ball before the first is empty.
+        let intersections: Vec<f32> = left
+            .iter()
+            .flat_map(|left| {
+                right
+                    .iter()
+                    .map(|right| left.intersection_cardinality(right))
+            })
+            .collect();
+        let intersection = |i: Option<usize>, j: Option<usize>| match (i, j) {
+            (Some(i), Some(j)) => intersections[i * hops + j],
+            _ => 0.0,
+        };
+        let left_cardinalities: Vec<f32> = left.iter().map(Self::cardinality).collect();
+        let right_cardinalities: Vec<f32> = right.iter().map(Self::cardinality).collect();
+
+        let last = Some(hops - 1);
+        for i in 0..hops {
+            let previous_i = i.checked_sub(1);
+            for j in 0..hops {
+                let previous_j = j.checked_sub(1);
+                overlaps[i * hops + j] = (intersection(Some(i), Some(j))
+                    - intersection(previous_i, Some(j))
+                    - intersection(Some(i), previous_j)
+                    + intersection(previous_i, previous_j))
+                .max(0.0);
+            }
+            let previous_left = previous_i.map_or(0.0, |i| left_cardinalities[i]);
+            let previous_right = previous_i.map_or(0.0, |i| right_cardinalities[i]);
+            left_difference[i] = (left_cardinalities[i] - previous_left
+                + intersection(previous_i, last)
+                - intersection(Some(i), last))
+            .max(0.0);
+            right_difference[i] = (right_cardinalities[i] - previous_right
+                + intersection(last, previous_i)
+                - intersection(last, Some(i)))
+            .max(0.0);
+        }
+
+        if normalize {
+            let mut normalizer = Normalized::new(hops);
+            for hop in 0..hops {
+                let previous_hop = hop.checked_sub(1);
+                normalizer.inc_left_cardinality(
+                    hop,
+                    (left_cardinalities[hop] - previous_hop.map_or(0.0, |h| left_cardinalities[h]))
+                        .max(0.0),
+                );
+                normalizer.inc_right_cardinality(
+                    hop,
+                    (right_cardinalities[hop]
+                        - previous_hop.map_or(0.0, |h| right_cardinalities[h]))
+                    .max(0.0),
+                );
+            }
+            normalizer.normalize(overlaps, left_difference, right_difference);
+        } else {
+            target.iter_mut().for_each(|value| *value = value.round());
+        }
+    }
+}
+
+impl<P: Precision + WordType<BITS>, const BITS: usize> SetSketch for HyperLogLog<P, BITS> {
+    const PRECISION: usize = P::EXPONENT;
+    const BITS: usize = BITS;
+
+    #[inline(always)]
+    fn insert_element<T: Hash>(&mut self, element: T) {
+        self.insert(element);
+    }
+
+    #[inline(always)]
+    fn union_assign(&mut self, other: &Self) {
+        *self = *self | other;
+    }
+
+    #[inline(always)]
+    fn cardinality(&self) -> f32 {
+        self.estimate_cardinality()
+    }
+
+    /// Writes the overlaps and differences estimated by the HyperLogLog
+    /// counters with their own normalization.
+    fn overlaps_and_differences(
+        left: &[Self],
+        right: &[Self],
+        normalize: bool,
+        target: &mut [f32],
+    ) {
+        let (overlaps, left_diffs, right_diffs) = if normalize {
+            <Self as HyperSpheresSketch<f32>>::normalized_overlap_and_differences_cardinality_matrices_vec(left, right)
+        } else {
+            <Self as HyperSpheresSketch<f32>>::overlap_and_differences_cardinality_matrices_vec(
+                left, right,
+            )
+        };
+        target
+            .iter_mut()
+            .zip(
+                overlaps
+                    .into_iter()
+                    .flatten()
+                    .chain(left_diffs)
+                    .chain(right_diffs),
+            )
+            .for_each(|(t, v)| *t = if normalize { v } else { v.round() });
+    }
+}
+
+/// Returns the 64-bit hash of the element, which is the same across runs,
+/// platforms and releases of Rust as long as the one of its [`Hash`]
+/// implementation is, as for the integer nodes.
+#[inline(always)]
+fn hash_element<T: Hash>(element: T) -> u64 {
+    let mut hasher = StableHasher::default();
+    element.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Returns the hash as a fraction of the hash space, in `(0, 1]`.
+#[inline(always)]
+fn unit(hash: u64) -> f64 {
+    (hash as f64 + 1.0) / 18_446_744_073_709_551_616.0
+}
+
+/// A MinHash sketch keeping the minimum of `K` independent hashes of the elements.
+///
+/// Its intersection estimate, the Jaccard index of the minima times the
+/// cardinality of the union, does not suffer from the inclusion-exclusion
+/// errors of the HyperLogLog counters when the intersection is small.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Epserde)]
+#[repr(C)]
+#[zero_copy]
+pub struct MinHash<const K: usize> {
+    minima: [u64; K],
+}
+
+impl<const K: usize> Default for MinHash<K> {
+    fn default() -> Self {
+        Self {
+            minima: [u64::MAX; K],
+        }
+    }
+}
+
+impl<const K: usize> MinHash<K> {
+    /// Returns whether no element was inserted in the sketch.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.minima.iter().all(|&minimum| minimum == u64::MAX)
+    }
+
+    /// Returns the estimated Jaccard index of the sketched sets.
+    pub fn jaccard(&self, other: &Self) -> f32 {
+        let matches = self
+            .minima
+            .iter()
+            .zip(&other.minima)
+            .filter(|&(&left, &right)| left == right && left != u64::MAX)
+            .count();
+        matches as f32 / K.max(1) as f32
+    }
+}
+
+impl<const K: usize> SetSketch for MinHash<K> {
+    const PRECISION: usize = K;
+    const BITS: usize = 64;
+
+    #[inline(always)]
+    fn insert_element<T: Hash>(&mut self, element: T) {
+        let hash = hash_element(element);
+        for (seed, minimum) in self.minima.iter_mut().enumerate() {
+            *minimum = (*minimum).min(splitmix64(hash ^ splitmix64(seed as u64)));
+        }
+    }
+
+    #[inline(always)]
+    fn union_assign(&mut self, other: &Self) {
+        for (minimum, &other) in self.minima.iter_mut().zip(&other.minima) {
+            *minimum = (*minimum).min(other);
+        }
+    }
+
+    /// Returns the estimated cardinality, as the minima of `n` uniform
+    /// hashes are exponentially distributed with rate `n`.
+    fn cardinality(&self) -> f32 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        let sum: f64 = self
+            .minima
+            .iter()
+            .map(|&minimum| -(-unit(minimum)).ln_1p())
+            .sum();
+        ((K as f64 - 1.0).max(1.0) / sum) as f32
+    }
+
+    fn intersection_cardinality(&self, other: &Self) -> f32 {
+        let mut union = *self;
+        union.union_assign(other);
+        self.jaccard(other) * union.cardinality()
+    }
+}
+
+/// A bottom-k sketch keeping the `K` smallest hashes of the elements.
+///
+/// Sets with fewer than `K` elements are represented exactly, so that the
+/// cardinalities and intersections of the small balls have no error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Epserde)]
+#[repr(C)]
+#[zero_copy]
+pub struct BottomK<const K: usize> {
+    /// The smallest hashes, sorted, of which only the first `len` are set.
+    hashes: [u64; K],
+    /// The number of hashes kept.
+    len: usize,
+}
+
+impl<const K: usize> Default for BottomK<K> {
+    fn default() -> Self {
+        Self {
+            hashes: [u64::MAX; K],
+            len: 0,
+        }
+    }
+}
+
+impl<const K: usize> BottomK<K> {
+    /// Returns the hashes kept by the sketch, sorted.
+    #[inline(always)]
+    pub fn hashes(&self) -> &[u64] {
+        &self.hashes[..self.len]
+    }
+
+    /// Returns whether the sketch holds every hash of the sketched set.
+    #[inline(always)]
+    pub fn is_exact(&self) -> bool {
+        self.len < K
+    }
+
+    /// Inserts the hash, unless it is already kept or larger than all the kept ones.
+    fn insert_hash(&mut self, hash: u64) {
+        let position = match self.hashes().binary_search(&hash) {
+            Ok(_) => return,
+            Err(position) => position,
+        };
+        if position == K {
+            return;
+        }
+        if self.len < K {
+            self.len += 1;
+        }
+        self.hashes
+            .copy_within(position..self.len - 1, position + 1);
+        self.hashes[position] = hash;
+    }
+}
+
+impl<const K: usize> SetSketch for BottomK<K> {
+    const PRECISION: usize = K;
+    const BITS: usize = 64;
+
+    #[inline(always)]
+    fn insert_element<T: Hash>(&mut self, element: T) {
+        self.insert_hash(splitmix64(hash_element(element)));
+    }
+
+    fn union_assign(&mut self, other: &Self) {
+        let (left, right) = (self.hashes(), other.hashes());
+        let mut merged = [u64::MAX; K];
+        let (mut i, mut j, mut len) = (0, 0, 0);
+        while len < K && (i < left.len() || j < right.len()) {
+            merged[len] = match (left.get(i), right.get(j)) {
+                (Some(&l), Some(&r)) if l == r => {
+                    i += 1;
+                    j += 1;
+                    l
+                }
+                (Some(&l), Some(&r)) if l < r => {
+                    i += 1;
+                    l
+                }
+                (_, Some(&r)) => {
+                    j += 1;
+                    r
+                }
+                (Some(&l), None) => {
+                    i += 1;
+                    l
+                }
+                (None, None) => unreachable!(),
+            };
+            len += 1;
+        }
+        self.hashes = merged;
+        self.len = len;
+    }
+
+    /// Returns the number of hashes when the sketch is exact, and otherwise
+    /// the estimate from the largest of the smallest hashes.
+    fn cardinality(&self) -> f32 {
+        if self.is_exact() {
+            self.len as f32
+        } else {
+            ((K as f64 - 1.0) / unit(self.hashes[K - 1])) as f32
+        }
+    }
+
+    /// Returns the fraction of the smallest hashes of the union kept by
+    /// both sketches times the cardinality of the union.
+    fn intersection_cardinality(&self, other: &Self) -> f32 {
+        let mut union = *self;
+        union.union_assign(other);
+        if union.len == 0 {
+            return 0.0;
+        }
+        let shared = union
+            .hashes()
+            .iter()
+            .filter(|&hash| {
+                self.hashes().binary_search(hash).is_ok()
+                    && other.hashes().binary_search(hash).is_ok()
+            })
+            .count();
+        shared as f32 / union.len as f32 * union.cardinality()
+    }
+}
@@ -0,0 +1,205 @@
+//! Module providing the graph-level statistics estimated from the counters of
+//! a fitted [`HyperSketching`], as HyperANF does with the neighbourhood function.
+//!
+//! The counters of the hop `i` estimate the balls of radius `i + 1` of the
+//! nodes, so that the sum of their cardinalities over all nodes estimates the
+//! number of pairs of nodes within distance `i + 1`. The differences of these
+//! sums give the distance distribution, truncated to the number of hops: the
+//! pairs farther apart than the last hop are not accounted for.
+//!
+//! The statistics are exact aggregates of the counters of all the nodes.
+//! Estimating them from a stream of sampled edges, without fitting the
+//! counters of the whole graph, is not supported yet and is left for later.
+use crate::sketches::SetSketch;
+use crate::{HyperSketching, HyperSketchingData};
+use anyhow::{ensure, Result};
+use graph::Graph;
+use irontraits::Sequence;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The statistics of a graph estimated by the counters of a [`HyperSketching`],
+/// which can be serialized e.g. to JSON.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphStatistics {
+    /// The number of nodes of the graph.
+    number_of_nodes: usize,
+    /// The number of hops the counters were fitted with.
+    number_of_hops: usize,
+    /// The estimated number of pairs of nodes within each distance, from 0 to
+    /// the number of hops.
+    neighbourhood_function: Vec<f64>,
+    /// The estimated fraction of the pairs of distinct nodes within the number
+    /// of hops at each distance, from 1 to the number of hops.
+    distance_distribution: Vec<f64>,
+    /// The estimated mean number of nodes within each distance from a node,
+    /// from 1 to the number of hops, the node included.
+    mean_reachable_set_sizes: Vec<f64>,
+    /// The estimated mean distance of the pairs of distinct nodes within the number of hops.
+    average_distance: f64,
+    /// The estimated shortest-paths index of dispersion, the ratio of the variance
+    /// of the distances to their mean.
+    spid: f64,
+}
+
+impl GraphStatistics {
+    /// Returns the statistics of the provided neighbourhood function.
+    ///
+    /// # Arguments
+    /// * `number_of_nodes`: usize - The number of nodes of the graph.
+    /// * `neighbourhood_function`: Vec<f64> - The number of pairs within each distance, from 0.
+    fn new(number_of_nodes: usize, mut neighbourhood_function: Vec<f64>) -> Self {
+        // The estimates of the counters of consecutive hops may decrease slightly.
+        for distance in 1..neighbourhood_function.len() {
+            neighbourhood_function[distance] =
+                neighbourhood_function[distance].max(neighbourhood_function[distance - 1]);
+        }
+        let number_of_hops = neighbourhood_function.len() - 1;
+        let reachable_pairs = neighbourhood_function[number_of_hops] - neighbourhood_function[0];
+
+        let distance_distribution: Vec<f64> = neighbourhood_function
+            .windows(2)
+            .map(|pairs| {
+                if reachable_pairs > 0.0 {
+                    (pairs[1] - pairs[0]) / reachable_pairs
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        let moment = |power: i32| {
+            distance_distribution
+                .iter()
+                .enumerate()
+                .map(|(distance, probability)| ((distance + 1) as f64).powi(power) * probability)
+                .sum::<f64>()
+        };
+        let average_distance = moment(1);
+        let spid = if average_distance > 0.0 {
+            (moment(2) - average_distance * average_distance).max(0.0) / average_distance
+        } else {
+            0.0
+        };
+
+        Self {
+            number_of_nodes,
+            number_of_hops,
+            mean_reachable_set_sizes: neighbourhood_function[1..]
+                .iter()
+                .map(|pairs| pairs / number_of_nodes.max(1) as f64)
+                .collect(),
+            neighbourhood_function,
+            distance_distribution,
+            average_distance,
+            spid,
+        }
+    }
+
+    /// Returns the number of nodes of the graph.
+    #[inline(always)]
+    pub fn number_of_nodes(&self) -> usize {
+        self.number_of_nodes
+    }
+
+    /// Returns the number of hops the counters were fitted with.
+    #[inline(always)]
+    pub fn number_of_hops(&self) -> usize {
+        self.number_of_hops
+    }
+
+    /// Returns the estimated number of pairs of nodes within each distance,
+    /// from 0 to the number of hops, where each node is within distance 0 of itself.
+    #[inline(always)]
+    pub fn neighbourhood_function(&self) -> &[f64] {
+        &self.neighbourhood_function
+    }
+
+    /// Returns the estimated fraction of the pairs of distinct nodes within
+    /// the number of hops at each distance, from 1 to the number of hops.
+    #[inline(always)]
+    pub fn distance_distribution(&self) -> &[f64] {
+        &self.distance_distribution
+    }
+
+    /// Returns the estimated mean number of nodes within each distance from
+    /// a node, from 1 to the number of hops, the node included.
+    #[inline(always)]
+    pub fn mean_reachable_set_sizes(&self) -> &[f64] {
+        &self.mean_reachable_set_sizes
+    }
+
+    /// Returns the estimated mean distance of the pairs of distinct nodes
+    /// within the number of hops, or 0 when there are none.
+    #[inline(always)]
+    pub fn average_distance(&self) -> f64 {
+        self.average_distance
+    }
+
+    /// Returns the estimated shortest-paths index of dispersion of the pairs
+    /// of distinct nodes within the number of hops, or 0 when there are none.
+    ///
+    /// Graphs with a spid smaller than 1 are usually considered "world-like",
+    /// and the ones with a larger spid "web-like".
+    #[inline(always)]
+    pub fn spid(&self) -> f64 {
+        self.spid
+    }
+}
+
+impl<G, Data, Counters, S> HyperSketching<G, Data, Counters, S>
+where
+    G: Graph,
+    Counters: Sequence<Item = S> + AsRef<[S]> + Sync,
+    S: SetSketch,
+    Data: AsRef<HyperSketchingData<Counters>>,
+{
+    /// Returns the graph-level statistics estimated by the counters of all
+    /// the nodes, summed in parallel without collecting the node features.
+    ///
+    /// As the distance distribution needs the balls of every radius, the
+    /// counters of all the hops must be retained.
+    pub fn statistics(&self) -> Result<GraphStatistics> {
+        let hops = self.retained_hops().len();
+        ensure!(
+            hops == self.number_of_hops(),
+            "The statistics require the counters of all the hops to be retained, \
+            but only the hops {:?} are.",
+            self.retained_hops()
+        );
+        let number_of_nodes = self.graph.number_of_nodes();
+        let counters = self.data.as_ref().counters.as_ref();
+        ensure!(
+            counters.len() == number_of_nodes * hops,
+            "The model must be fitted before computing the statistics."
+        );
+
+        let sums = counters
+            .par_chunks_exact(hops)
+            .fold(
+                || vec![0.0_f64; hops],
+                |mut sums, node_counters| {
+                    for (sum, counter) in sums.iter_mut().zip(node_counters) {
+                        *sum += counter.cardinality() as f64;
+                    }
+                    sums
+                },
+            )
+            .reduce(
+                || vec![0.0_f64; hops],
+                |mut sums, other| {
+                    for (sum, other) in sums.iter_mut().zip(other) {
+                        *sum += other;
+                    }
+                    sums
+                },
+            );
+
+        let mut neighbourhood_function = Vec::with_capacity(hops + 1);
+        neighbourhood_function.push(number_of_nodes as f64);
+        neighbourhood_function.extend(sums);
+        Ok(GraphStatistics::new(
+            number_of_nodes,
+            neighbourhood_function,
+        ))
+    }
+}
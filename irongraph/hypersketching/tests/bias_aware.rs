@@ -0,0 +1,166 @@
+use algorithms::features::EdgeFeatures;
+use csr::generators::erdos_renyi_gnm;
+use csr::prelude::*;
+use graph::*;
+use hyperloglog_rs::prelude::*;
+use hypersketching::*;
+use rayon::prelude::*;
+
+type Counters = Vec<HyperLogLog<Precision12, 6>>;
+type Sketching = HyperSketching<VecCSR<usize>, HyperSketchingData<Counters>, Counters>;
+
+/// Returns the hop of each node within the provided number of hops from the
+/// root, where the root and its neighbours both belong to the hop 0.
+fn hops_from(
+    number_of_nodes: usize,
+    root: usize,
+    hops: usize,
+    neighbours: impl Fn(usize) -> Vec<usize>,
+) -> Vec<Option<usize>> {
+    let mut distances = vec![usize::MAX; number_of_nodes];
+    distances[root] = 0;
+    let mut frontier = vec![root];
+    for distance in 1..=hops {
+        let mut next = Vec::new();
+        for node in frontier {
+            for neighbour in neighbours(node) {
+                if distances[neighbour] == usize::MAX {
+                    distances[neighbour] = distance;
+                    next.push(neighbour);
+                }
+            }
+        }
+        frontier = next;
+    }
+    distances
+        .into_iter()
+        .map(|distance| (distance <= hops).then(|| distance.max(1) - 1))
+        .collect()
+}
+
+/// Returns the bias-aware features of the edge computed with explicit
+/// breadth-first visits, ignoring the edge itself unless it is inserted.
+fn exact_features(
+    graph: &VecCSR<usize>,
+    (src, dst): (usize, usize),
+    hops: usize,
+    insert_edge: bool,
+) -> Vec<f32> {
+    let undirected = graph.undirected();
+    let neighbours = |node: usize| {
+        let mut neighbours: Vec<usize> = graph
+            .successors(node)
+            .into_iter()
+            .filter(|&neighbour| {
+                !((node == src && neighbour == dst)
+                    || (undirected && node == dst && neighbour == src))
+            })
+            .collect();
+        if insert_edge && node == src {
+            neighbours.push(dst);
+        }
+        if insert_edge && undirected && node == dst {
+            neighbours.push(src);
+        }
+        neighbours
+    };
+    let left = hops_from(graph.number_of_nodes(), src, hops, neighbours);
+    let right = hops_from(graph.number_of_nodes(), dst, hops, neighbours);
+
+    let mut features = vec![0.0; hops * hops + 2 * hops];
+    for (left, right) in left.into_iter().zip(right) {
+        match (left, right) {
+            (Some(i), Some(j)) => features[i * hops + j] += 1.0,
+            (Some(i), None) => features[hops * hops + i] += 1.0,
+            (None, Some(j)) => features[hops * hops + hops + j] += 1.0,
+            (None, None) => {}
+        }
+    }
+    features
+}
+
+#[test]
+fn test_bias_aware_features_against_exact_visits() {
+    let graph: VecCSR<usize> = erdos_renyi_gnm(100, 300, 42);
+    let mut edges: Vec<(usize, usize)> = (0..graph.number_of_nodes())
+        .flat_map(|src| graph.successors(src).into_iter().map(move |dst| (src, dst)))
+        .step_by(7)
+        .collect();
+    // Pairs of nodes which are mostly not connected.
+    edges.extend(
+        (0..50)
+            .map(|src| (src, (src * 37 + 11) % 100))
+            .filter(|(src, dst)| src != dst),
+    );
+
+    for hops in 1..=3 {
+        let mut sketching = Sketching::new(hops, false, graph.clone()).unwrap();
+        sketching.fit();
+        assert_eq!(sketching.feature_len(), hops * hops + 2 * hops);
+        assert_eq!(sketching.feature_names().len(), sketching.feature_len());
+
+        let features = sketching
+            .bias_aware_features::<false>()
+            .par_edge_features_matrix(edges.par_iter().copied());
+        let inserted = sketching
+            .bias_aware_features::<true>()
+            .par_edge_features_matrix(edges.par_iter().copied());
+
+        for (position, &edge) in edges.iter().enumerate() {
+            let row = position * sketching.feature_len()..(position + 1) * sketching.feature_len();
+            assert_eq!(
+                features[row.clone()],
+                exact_features(&graph, edge, hops, false),
+                "edge {:?} with {} hops",
+                edge,
+                hops
+            );
+            assert_eq!(
+                inserted[row],
+                exact_features(&graph, edge, hops, true),
+                "inserted edge {:?} with {} hops",
+                edge,
+                hops
+            );
+        }
+    }
+}
+
+#[test]
+fn test_bias_aware_features_outside_of_rayon() {
+    let graph: VecCSR<usize> = erdos_renyi_gnm(100, 300, 43);
+    let sketching = Sketching::new(2, false, graph.clone()).unwrap();
+    let edges: Vec<(usize, usize)> = (0..100).map(|src| (src, (src * 13 + 5) % 100)).collect();
+
+    // Threads outside of the rayon pools, and a pool larger than the
+    // global one, each compute the features with their own masks.
+    let expected = sketching
+        .bias_aware_features::<false>()
+        .par_edge_features_matrix(edges.par_iter().copied());
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                let features = sketching.bias_aware_features::<false>();
+                for (position, &edge) in edges.iter().enumerate() {
+                    let mut row = vec![0.0; features.feature_len()];
+                    features.edge_features(edge, &mut row);
+                    assert_eq!(row, exact_features(&graph, edge, 2, false));
+                    assert_eq!(
+                        row,
+                        expected[position * row.len()..(position + 1) * row.len()]
+                    );
+                }
+            });
+        }
+    });
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(rayon::current_num_threads() * 2 + 1)
+        .build()
+        .unwrap();
+    let features = pool.install(|| {
+        sketching
+            .bias_aware_features::<false>()
+            .par_edge_features_matrix(edges.par_iter().copied())
+    });
+    assert_eq!(features, expected);
+}
@@ -0,0 +1,83 @@
+use algorithms::features::EdgeFeatures;
+use csr::generators::rmat;
+use csr::prelude::*;
+use hypersketching::directed::*;
+use hypersketching::sketches::*;
+use hypersketching::*;
+use rayon::prelude::*;
+
+type Sketch = BottomK<64>;
+type Directed = DirectedHyperSketching<VecCSR<usize>, VecCSR<usize>, Vec<Sketch>, Sketch>;
+type Sketching =
+    HyperSketching<VecCSR<usize>, HyperSketchingData<Vec<Sketch>>, Vec<Sketch>, Sketch>;
+
+fn chain() -> VecCSR<usize> {
+    // The chain 0 -> 1 -> 2 -> 3, with the edge 4 -> 2.
+    let edges: Vec<(usize, usize)> = vec![(0, 1), (1, 2), (2, 3), (4, 2)];
+    CSRBuilder::default()
+        .number_of_edges(edges.len())
+        .number_of_nodes(5)
+        .sorted()
+        .build(edges.iter().copied())
+}
+
+#[test]
+fn test_out_balls_against_in_balls() {
+    let graph = chain();
+    let transposed = graph.transpose();
+    let mut sketching = Directed::new(2, false, Some(graph), Some(transposed)).unwrap();
+    sketching.fit();
+
+    let features = sketching.biased_features(Ball::Out, Ball::In).unwrap();
+    assert_eq!(features.feature_len(), 8);
+    // The out-ball of 0 is {0, 1} and then {2}, the in-ball of 3 is {3, 2} and then {1, 4}.
+    assert_eq!(
+        features.par_edge_features_matrix(vec![(0, 3)]),
+        vec![0.0, 1.0, 1.0, 0.0, 1.0, 0.0, 1.0, 1.0]
+    );
+}
+
+#[test]
+fn test_balls_of_the_same_direction_match_hyper_sketching() {
+    let graph: VecCSR<usize> = rmat(8, 1_024, [0.57, 0.19, 0.19], 41);
+    let transposed = graph.transpose();
+    let edges: Vec<(usize, usize)> = (0..256).map(|src| (src, (src * 13 + 1) % 256)).collect();
+
+    let mut sketching =
+        Directed::new(2, true, Some(graph.clone()), Some(transposed.clone())).unwrap();
+    sketching.fit();
+
+    for (ball, graph) in [(Ball::Out, graph), (Ball::In, transposed)] {
+        let mut expected = Sketching::new(2, true, graph).unwrap();
+        expected.fit();
+        assert_eq!(
+            sketching
+                .biased_features(ball, ball)
+                .unwrap()
+                .par_edge_features_matrix(edges.par_iter().copied()),
+            expected
+                .biased_features::<false>()
+                .par_edge_features_matrix(edges.par_iter().copied())
+        );
+    }
+}
+
+#[test]
+fn test_missing_balls() {
+    let graph = chain();
+    let mut sketching = Directed::new(2, false, Some(graph.clone()), None).unwrap();
+    sketching.fit();
+    assert!(sketching.has_balls(Ball::Out));
+    assert!(!sketching.has_balls(Ball::In));
+    assert!(sketching.biased_features(Ball::Out, Ball::Out).is_ok());
+    assert!(sketching.biased_features(Ball::Out, Ball::In).is_err());
+
+    assert!(Directed::new(2, false, None, None).is_err());
+    let out_balls = Sketching::new(2, false, graph.clone()).unwrap();
+    let in_balls = Sketching::new(3, false, graph.transpose()).unwrap();
+    assert!(Directed::from_sketchings(Some(out_balls), Some(in_balls)).is_err());
+
+    let out_balls = Sketching::new(2, false, graph.clone()).unwrap();
+    let in_balls = Sketching::new(2, true, graph.transpose()).unwrap();
+    assert!(Directed::from_sketchings(Some(out_balls), Some(in_balls)).is_err());
+}
@@ -0,0 +1,135 @@
+use algorithms::features::EdgeFeatures;
+use csr::generators::erdos_renyi_gnm;
+use csr::prelude::*;
+use hyperloglog_rs::prelude::*;
+use hypersketching::dynamic::*;
+use hypersketching::sketches::BottomK;
+use hypersketching::*;
+use rayon::prelude::*;
+
+type Counters = Vec<HyperLogLog<Precision10, 5>>;
+type Sketching = HyperSketching<
+    VecCSR<usize>,
+    HyperSketchingData<Counters>,
+    Counters,
+    HyperLogLog<Precision10, 5>,
+>;
+type BottomKSketching =
+    HyperSketching<VecCSR<usize>, HyperSketchingData<Vec<BottomK<8>>>, Vec<BottomK<8>>, BottomK<8>>;
+
+#[test]
+fn test_dynamic_matches_static_configuration() {
+    let graph: VecCSR<usize> = erdos_renyi_gnm(200, 600, 11);
+    let edges: Vec<(usize, usize)> = (0..200).map(|src| (src, (src * 13 + 5) % 200)).collect();
+
+    let mut dynamic = DynamicHyperSketching::new(10, 5, 2, false, graph.clone()).unwrap();
+    assert!(matches!(dynamic, DynamicHyperSketching::P10B5(_)));
+    dynamic.fit();
+    let mut sketching = Sketching::new(2, false, graph.clone()).unwrap();
+    sketching.fit();
+
+    assert_eq!((dynamic.precision(), dynamic.bits()), (10, 5));
+    assert_eq!(dynamic.feature_names(), sketching.feature_names());
+    assert_eq!(
+        dynamic
+            .biased_features::<false>()
+            .par_edge_features_matrix(edges.par_iter().copied()),
+        sketching
+            .biased_features::<false>()
+            .par_edge_features_matrix(edges.par_iter().copied())
+    );
+    assert_eq!(
+        dynamic
+            .bias_aware_features::<true>()
+            .par_edge_features_matrix(edges.par_iter().copied()),
+        sketching
+            .bias_aware_features::<true>()
+            .par_edge_features_matrix(edges.par_iter().copied())
+    );
+
+    let error = DynamicHyperSketching::new(11, 6, 2, false, graph)
+        .err()
+        .unwrap();
+    assert!(error.to_string().contains("not supported"));
+    assert!(SUPPORTED_CONFIGURATIONS.contains(&(12, 6)));
+}
+
+#[test]
+fn test_dynamic_store_and_load() {
+    let graph: VecCSR<usize> = erdos_renyi_gnm(100, 300, 12);
+    let edges: Vec<(usize, usize)> = (0..100).map(|src| (src, (src * 7 + 3) % 100)).collect();
+    let mut dynamic = DynamicHyperSketching::new(14, 6, 2, true, graph.clone()).unwrap();
+    dynamic.fit();
+    let features = dynamic
+        .biased_features::<false>()
+        .par_edge_features_matrix(edges.par_iter().copied());
+
+    let path = std::env::temp_dir().join("hypersketching_dynamic.hs");
+    dynamic.store(&path).unwrap();
+
+    // The configuration is read from the stored data.
+    let loaded = unsafe { DynamicHyperSketching::load(&path, graph.clone(), true) }.unwrap();
+    assert!(matches!(loaded, DynamicHyperSketching::P14B6(_)));
+    assert!(loaded.normalize());
+    assert_eq!(loaded.graph_hash(), dynamic.graph_hash());
+
+    let mapped = unsafe { DynamicHyperSketching::mmap(&path, graph.clone(), true) }.unwrap();
+    assert!(matches!(mapped, DynamicHyperSketching::P14B6(_)));
+    assert_eq!((mapped.precision(), mapped.bits()), (14, 6));
+    assert_eq!(
+        mapped
+            .biased_features::<false>()
+            .par_edge_features_matrix(edges.par_iter().copied()),
+        features
+    );
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_dynamic_load_of_unsupported_data() {
+    let graph: VecCSR<usize> = erdos_renyi_gnm(100, 300, 13);
+    let mut sketching = BottomKSketching::new(2, false, graph.clone()).unwrap();
+    sketching.fit();
+    let path = std::env::temp_dir().join("hypersketching_dynamic_bottom_k.hs");
+    sketching.store(&path).unwrap();
+
+    // The counters of the stored data are not the ones of any supported configuration.
+    let error = unsafe { DynamicHyperSketching::load(&path, graph.clone(), true) }
+        .err()
+        .unwrap();
+    assert!(error.to_string().contains("supported configurations"));
+    assert!(unsafe { DynamicHyperSketching::mmap(&path, graph.clone(), true) }.is_err());
+    std::fs::remove_file(&path).unwrap();
+
+    // A missing file is reported as such.
+    let error = unsafe { DynamicHyperSketching::load(&path, graph, true) }
+        .err()
+        .unwrap();
+    assert!(error.to_string().contains("Error while loading"));
+}
+
+#[test]
+fn test_dynamic_load_of_truncated_data() {
+    let graph: VecCSR<usize> = erdos_renyi_gnm(100, 300, 14);
+    let mut dynamic = DynamicHyperSketching::new(12, 6, 2, false, graph.clone()).unwrap();
+    dynamic.fit();
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join("truncated.hs");
+    dynamic.store(&path).unwrap();
+
+    // The stored data has a supported configuration but is cut short, so the
+    // error of reading it is returned instead of an unsupported configuration.
+    let length = std::fs::metadata(&path).unwrap().len();
+    std::fs::OpenOptions::new()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_len(length / 2)
+        .unwrap();
+    let error = unsafe { DynamicHyperSketching::load(&path, graph, true) }
+        .err()
+        .unwrap();
+    assert!(error.to_string().contains("Error while loading"));
+    assert!(!format!("{:#}", error).contains("supported configurations"));
+}
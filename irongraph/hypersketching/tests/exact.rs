@@ -0,0 +1,63 @@
+use algorithms::features::EdgeFeatures;
+use csr::generators::{erdos_renyi_gnm, path};
+use csr::prelude::*;
+use graph::*;
+use hyperloglog_rs::prelude::*;
+use hypersketching::exact::*;
+use hypersketching::*;
+use rayon::prelude::*;
+
+type Counters = Vec<HyperLogLog<Precision12, 6>>;
+type Sketching = HyperSketching<VecCSR<usize>, HyperSketchingData<Counters>, Counters>;
+
+#[test]
+fn test_exact_features_on_path() {
+    let graph: VecCSR<usize> = path(6);
+    let features = ExactOverlapFeatures::new(2, false, &graph).unwrap();
+    assert_eq!(features.feature_len(), 8);
+    assert_eq!(
+        features.par_edge_features_matrix(vec![(1, 4)]),
+        vec![0.0, 1.0, 1.0, 0.0, 2.0, 0.0, 2.0, 0.0]
+    );
+    assert!(ExactOverlapFeatures::new(0, false, &graph).is_err());
+}
+
+#[test]
+fn test_exact_features_match_bias_aware_features_on_non_edges() {
+    let graph: VecCSR<usize> = erdos_renyi_gnm(100, 300, 3);
+    let non_edges: Vec<(usize, usize)> = (0..100)
+        .map(|src| (src, (src * 37 + 11) % 100))
+        .filter(|&(src, dst)| src != dst && !graph.has_successor(src, dst))
+        .collect();
+
+    for normalize in [false, true] {
+        let mut sketching = Sketching::new(3, normalize, graph.clone()).unwrap();
+        sketching.fit();
+        assert_eq!(
+            sketching
+                .exact_features()
+                .par_edge_features_matrix(non_edges.par_iter().copied()),
+            sketching
+                .bias_aware_features::<false>()
+                .par_edge_features_matrix(non_edges.par_iter().copied())
+        );
+    }
+}
+
+#[test]
+fn test_accuracy_report() {
+    let graph: VecCSR<usize> = erdos_renyi_gnm(500, 1_500, 5);
+    let edges: Vec<(usize, usize)> = (0..500).map(|src| (src, (src * 7 + 3) % 500)).collect();
+
+    let mut sketching = Sketching::new(2, true, graph).unwrap();
+    sketching.fit();
+    let report = sketching.accuracy_report(edges.par_iter().copied());
+
+    assert_eq!(report.precision(), 12);
+    assert_eq!(report.bits(), 6);
+    assert_eq!(report.number_of_edges(), edges.len());
+    assert_eq!(report.features().len(), sketching.feature_len());
+    assert_eq!(report.features()[0].name(), "overlap_h0_h0");
+    // The balls are small enough for the counters to be nearly exact.
+    assert!(report.mean_absolute_error() < 1.0, "{}", report);
+}
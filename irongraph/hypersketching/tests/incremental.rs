@@ -0,0 +1,157 @@
+use algorithms::features::EdgeFeatures;
+use csr::generators::{erdos_renyi_gnm, rmat};
+use csr::prelude::*;
+use graph::*;
+use hyperloglog_rs::prelude::*;
+use hypersketching::*;
+use rayon::prelude::*;
+
+type Counters = Vec<HyperLogLog<Precision12, 6>>;
+type Sketching = HyperSketching<VecCSR<usize>, HyperSketchingData<Counters>, Counters>;
+
+/// Returns the provided edges followed by their reverse.
+fn both_directions(edges: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    edges
+        .iter()
+        .flat_map(|&(src, dst)| [(src, dst), (dst, src)])
+        .collect()
+}
+
+/// Returns the graph with the edges of the graph and the new edges.
+fn with_edges(
+    graph: &VecCSR<usize>,
+    number_of_nodes: usize,
+    edges: &[(usize, usize)],
+) -> VecCSR<usize> {
+    let mut all_edges: Vec<(usize, usize)> = (0..graph.number_of_nodes())
+        .flat_map(|src| graph.successors(src).into_iter().map(move |dst| (src, dst)))
+        .chain(edges.iter().copied())
+        .collect();
+    all_edges.sort_unstable();
+    all_edges.dedup();
+    CSRBuilder::default()
+        .number_of_nodes(number_of_nodes)
+        .number_of_edges(all_edges.len())
+        .sorted()
+        .build(all_edges)
+}
+
+#[test]
+fn test_insert_edges_matches_fit() {
+    let graph: VecCSR<usize> = erdos_renyi_gnm(150, 300, 21);
+    // New edges between the previous nodes and towards ten new nodes.
+    let new_edges: Vec<(usize, usize)> = (0..40)
+        .map(|i| (i * 3, (i * 11 + 7) % 160))
+        .filter(|&(src, dst)| src != dst && (dst >= 150 || !graph.has_successor(src, dst)))
+        .collect();
+    let new_edges = both_directions(&new_edges);
+    let updated = with_edges(&graph, 160, &new_edges);
+    let pairs: Vec<(usize, usize)> = (0..160).map(|src| (src, (src * 17 + 3) % 160)).collect();
+
+    for hops in 1..=3 {
+        let mut sketching = Sketching::new(hops, false, graph.clone()).unwrap();
+        sketching.fit();
+        let previous_hash = sketching.graph_hash();
+        sketching.insert_edges(updated.clone(), &new_edges).unwrap();
+
+        let mut refitted = Sketching::new(hops, false, updated.clone()).unwrap();
+        refitted.fit();
+
+        assert_ne!(sketching.graph_hash(), previous_hash);
+        assert_eq!(sketching.graph_hash(), refitted.graph_hash());
+        assert_eq!(
+            sketching
+                .biased_features::<false>()
+                .par_edge_features_matrix(pairs.par_iter().copied()),
+            refitted
+                .biased_features::<false>()
+                .par_edge_features_matrix(pairs.par_iter().copied())
+        );
+        assert_eq!(
+            sketching
+                .bias_aware_features::<false>()
+                .par_edge_features_matrix(pairs.par_iter().copied()),
+            refitted
+                .bias_aware_features::<false>()
+                .par_edge_features_matrix(pairs.par_iter().copied())
+        );
+    }
+}
+
+#[test]
+fn test_insert_edges_requires_fit_and_nodes() {
+    let graph: VecCSR<usize> = erdos_renyi_gnm(50, 100, 22);
+    let mut sketching = Sketching::new(2, false, graph.clone()).unwrap();
+    assert!(sketching.insert_edges(graph, &[]).is_err());
+
+    sketching.fit();
+    let smaller: VecCSR<usize> = erdos_renyi_gnm(40, 80, 22);
+    assert!(sketching.insert_edges(smaller, &[]).is_err());
+}
+
+#[test]
+fn test_insert_edges_checks_the_updated_graph() {
+    let graph: VecCSR<usize> = erdos_renyi_gnm(50, 100, 23);
+    let (src, dst) = (0..50)
+        .flat_map(|src| (0..50).map(move |dst| (src, dst)))
+        .find(|&(src, dst)| src != dst && !graph.has_successor(src, dst))
+        .unwrap();
+    let inserted = both_directions(&[(src, dst)]);
+    let updated = with_edges(&graph, 50, &inserted);
+    let mut sketching = Sketching::new(2, false, graph.clone()).unwrap();
+    sketching.fit();
+    let graph_hash = sketching.graph_hash();
+
+    // An endpoint outside of the updated graph.
+    assert!(sketching
+        .insert_edges(updated.clone(), &[(src, 50)])
+        .is_err());
+    // An inserted edge missing from the updated graph.
+    assert!(sketching
+        .insert_edges(graph.clone(), &[(src, dst)])
+        .is_err());
+    // An edge of the updated graph which is not inserted.
+    assert!(sketching.insert_edges(updated.clone(), &[]).is_err());
+    // An edge of the previous graph missing from the updated graph.
+    let other: VecCSR<usize> = erdos_renyi_gnm(50, 100, 24);
+    let other_edges: Vec<(usize, usize)> = (0..50)
+        .flat_map(|src| other.successors(src).into_iter().map(move |dst| (src, dst)))
+        .collect();
+    assert!(sketching.insert_edges(other, &other_edges).is_err());
+
+    // The model is left as it was.
+    assert_eq!(sketching.graph_hash(), graph_hash);
+    sketching.insert_edges(updated, &inserted).unwrap();
+    assert_ne!(sketching.graph_hash(), graph_hash);
+}
+
+#[test]
+fn test_insert_edges_into_a_directed_graph() {
+    let graph: VecCSR<usize> = rmat(6, 200, [0.57, 0.19, 0.19], 25);
+    let (src, dst) = (0..64)
+        .flat_map(|src| (0..64).map(move |dst| (src, dst)))
+        .find(|&(src, dst)| {
+            src != dst && !graph.has_successor(src, dst) && !graph.has_successor(dst, src)
+        })
+        .unwrap();
+    let mut sketching = Sketching::new(2, false, graph.clone()).unwrap();
+    sketching.fit();
+    let graph_hash = sketching.graph_hash();
+
+    // The reverse of an inserted edge is not inserted in a directed graph.
+    let symmetric = with_edges(&graph, 64, &both_directions(&[(src, dst)]));
+    assert!(sketching.insert_edges(symmetric, &[(src, dst)]).is_err());
+    assert_eq!(sketching.graph_hash(), graph_hash);
+
+    let updated = with_edges(&graph, 64, &[(src, dst)]);
+    sketching
+        .insert_edges(updated.clone(), &[(src, dst)])
+        .unwrap();
+    let mut refitted = Sketching::new(2, false, updated).unwrap();
+    refitted.fit();
+    assert_eq!(sketching.graph_hash(), refitted.graph_hash());
+    assert_eq!(
+        sketching.node_features_matrix(),
+        refitted.node_features_matrix()
+    );
+}
@@ -0,0 +1,66 @@
+use algorithms::features::EdgeFeatures;
+use csr::generators::erdos_renyi_gnm;
+use csr::prelude::*;
+use hypersketching::layout::FeatureLayout;
+use hypersketching::sketches::*;
+use hypersketching::*;
+
+type Sketching = HyperSketching<
+    VecCSR<usize>,
+    HyperSketchingData<Vec<BottomK<64>>>,
+    Vec<BottomK<64>>,
+    BottomK<64>,
+>;
+
+#[test]
+fn test_feature_layout_of_the_retained_hops() {
+    let graph: VecCSR<usize> = erdos_renyi_gnm(100, 300, 53);
+    let mut sketching = Sketching::new(4, false, graph).unwrap();
+    sketching.set_retained_hops(&[1, 3]).unwrap();
+    sketching.fit();
+
+    let layout = sketching.feature_layout();
+    assert_eq!(layout, FeatureLayout::new(&[1, 3]));
+    assert_eq!(layout.hops(), &[1, 3]);
+    assert_eq!(layout.len(), sketching.feature_len());
+    assert_eq!(layout.names(), sketching.feature_names());
+
+    for (column, name) in layout.names().iter().enumerate() {
+        assert_eq!(layout.column(name), Some(column));
+    }
+    assert_eq!(layout.overlap_column(3, 1), Some(2));
+    assert_eq!(layout.left_difference_column(3), Some(5));
+    assert_eq!(layout.right_difference_column(1), Some(6));
+    assert_eq!(layout.overlap_column(0, 1), None);
+    assert_eq!(layout.left_difference_column(2), None);
+    assert_eq!(layout.column("left_diff_h0"), None);
+    assert_eq!(layout.column("overlap_h01_h3"), None);
+    assert_eq!(layout.column("overlap_h1_h+3"), None);
+    assert_eq!(layout.column("overlap_h1"), None);
+    assert_eq!(layout.column("right_diff_h3_h1"), None);
+    assert_eq!(layout.column("cardinality_h1"), None);
+
+    let features = sketching.biased_features::<false>();
+    let mut row = vec![0.0; layout.len()];
+    features.edge_features((0, 1), &mut row);
+    let (overlaps, left_differences, right_differences) = layout.split(&row);
+    assert_eq!(overlaps, &row[..4]);
+    assert_eq!(left_differences, &row[4..6]);
+    assert_eq!(right_differences, &row[6..]);
+    assert_eq!(layout.overlaps(&row), overlaps);
+    assert_eq!(layout.left_differences(&row), left_differences);
+    assert_eq!(layout.right_differences(&row), right_differences);
+    assert_eq!(overlaps[1], row[layout.column("overlap_h1_h3").unwrap()]);
+}
+
+#[test]
+#[should_panic]
+fn test_feature_layout_of_unsorted_hops() {
+    FeatureLayout::new(&[2, 1]);
+}
+
+#[test]
+#[should_panic]
+fn test_split_of_features_of_another_layout() {
+    FeatureLayout::new(&[0, 1]).split(&[0.0; 3]);
+}
@@ -0,0 +1,53 @@
+use csr::generators::{erdos_renyi_gnm, path};
+use csr::prelude::*;
+use hyperloglog_rs::prelude::*;
+use hypersketching::sketches::*;
+use hypersketching::*;
+
+type SketchingWith<S> = HyperSketching<VecCSR<usize>, HyperSketchingData<Vec<S>>, Vec<S>, S>;
+
+#[test]
+fn test_node_features_on_path() {
+    let graph: VecCSR<usize> = path(6);
+    let mut sketching = SketchingWith::<BottomK<128>>::new(3, false, graph).unwrap();
+    sketching.fit();
+
+    assert_eq!(sketching.node_feature_len(), 3);
+    assert_eq!(
+        sketching.node_feature_names(),
+        vec!["cardinality_h0", "cardinality_h1", "cardinality_h2"]
+    );
+    let features = sketching.node_features_matrix();
+    assert_eq!(features.len(), 6 * 3);
+    for (node, row) in features.chunks_exact(3).enumerate() {
+        for (hop, &cardinality) in row.iter().enumerate() {
+            let radius = hop + 1;
+            let ball = (node + radius).min(5) - node.saturating_sub(radius) + 1;
+            assert_eq!(cardinality, ball as f32, "node {} hop {}", node, hop);
+        }
+    }
+}
+
+#[test]
+fn test_node_features_grow_with_the_hops() {
+    let graph: VecCSR<usize> = erdos_renyi_gnm(300, 900, 13);
+    let mut sketching = SketchingWith::<HyperLogLog<Precision12, 6>>::new(3, true, graph).unwrap();
+    sketching.fit();
+
+    let mut target = vec![-1.0; 300 * 3];
+    sketching.node_features(&mut target);
+    assert_eq!(target, sketching.node_features_matrix());
+    for row in target.chunks_exact(3) {
+        assert!(row[0] > 0.5);
+        // The estimates may only decrease by the error of the counters.
+        assert!(row.windows(2).all(|pair| pair[0] <= pair[1] * 1.05));
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_node_features_require_fit() {
+    let graph: VecCSR<usize> = path(6);
+    let sketching = SketchingWith::<BottomK<128>>::new(2, false, graph).unwrap();
+    sketching.node_features_matrix();
+}
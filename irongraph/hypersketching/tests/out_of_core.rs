@@ -0,0 +1,108 @@
+use algorithms::features::EdgeFeatures;
+use core::hash::Hash;
+use csr::generators::erdos_renyi_gnm;
+use csr::prelude::*;
+use epserde::Epserde;
+use hypersketching::sketches::*;
+use hypersketching::*;
+use rayon::prelude::*;
+
+type Counters = Vec<BottomK<32>>;
+type Sketching = HyperSketching<VecCSR<usize>, HyperSketchingData<Counters>, Counters, BottomK<32>>;
+
+/// A bottom-k sketch with a 16-byte field, so that the counters stored after
+/// a length ending at an odd multiple of 8 bytes need padding.
+#[derive(Debug, Clone, Copy, Default, Epserde)]
+#[repr(C)]
+#[zero_copy]
+struct WideBottomK {
+    sketch: BottomK<8>,
+    wide: u128,
+}
+
+impl SetSketch for WideBottomK {
+    const PRECISION: usize = 8;
+    const BITS: usize = 64;
+
+    fn insert_element<T: Hash>(&mut self, element: T) {
+        self.sketch.insert_element(element);
+    }
+
+    fn union_assign(&mut self, other: &Self) {
+        self.sketch.union_assign(&other.sketch);
+    }
+
+    fn cardinality(&self) -> f32 {
+        self.sketch.cardinality()
+    }
+
+    fn intersection_cardinality(&self, other: &Self) -> f32 {
+        self.sketch.intersection_cardinality(&other.sketch)
+    }
+}
+
+type WideCounters = Vec<WideBottomK>;
+type WideSketching =
+    HyperSketching<VecCSR<usize>, HyperSketchingData<WideCounters>, WideCounters, WideBottomK>;
+
+#[test]
+fn test_fit_to_file_matches_fit_and_store() {
+    let graph: VecCSR<usize> = erdos_renyi_gnm(300, 1_000, 17);
+    let edges: Vec<(usize, usize)> = (0..300).map(|src| (src, (src * 11 + 5) % 300)).collect();
+    let directory = tempfile::tempdir().unwrap();
+
+    let mut sketching = Sketching::new(3, true, graph.clone()).unwrap();
+    let fitted_path = directory.path().join("fitted.hs");
+    sketching.fit_to_file(&fitted_path).unwrap();
+    // The model itself is not fitted by the out-of-core fit.
+    assert_eq!(sketching.graph_hash(), None);
+
+    sketching.fit();
+    let stored_path = directory.path().join("stored.hs");
+    sketching.store(&stored_path).unwrap();
+    assert_eq!(
+        std::fs::read(&fitted_path).unwrap(),
+        std::fs::read(&stored_path).unwrap()
+    );
+
+    let loaded = unsafe { Sketching::load(&fitted_path, graph, true) }.unwrap();
+    assert_eq!(loaded.graph_hash(), sketching.graph_hash());
+    assert_eq!(
+        loaded
+            .biased_features::<false>()
+            .par_edge_features_matrix(edges.par_iter().copied()),
+        sketching
+            .biased_features::<false>()
+            .par_edge_features_matrix(edges.par_iter().copied())
+    );
+}
+
+#[test]
+fn test_fit_to_file_with_padded_counters() {
+    let graph: VecCSR<usize> = erdos_renyi_gnm(100, 300, 18);
+    let directory = tempfile::tempdir().unwrap();
+
+    // The retained hops differ by one usize, so that the counters need
+    // padding to be aligned to 16 bytes in one of the two files.
+    for retained_hops in [&[2][..], &[1, 2][..]] {
+        let mut sketching = WideSketching::new(3, false, graph.clone()).unwrap();
+        sketching.set_retained_hops(retained_hops).unwrap();
+        let fitted_path = directory.path().join("fitted.hs");
+        sketching.fit_to_file(&fitted_path).unwrap();
+
+        sketching.fit();
+        let stored_path = directory.path().join("stored.hs");
+        sketching.store(&stored_path).unwrap();
+        assert_eq!(
+            std::fs::read(&fitted_path).unwrap(),
+            std::fs::read(&stored_path).unwrap()
+        );
+
+        let loaded = unsafe { WideSketching::load(&fitted_path, graph.clone(), true) }.unwrap();
+        assert_eq!(loaded.retained_hops(), retained_hops);
+        assert_eq!(
+            loaded.node_features_matrix(),
+            sketching.node_features_matrix()
+        );
+    }
+}
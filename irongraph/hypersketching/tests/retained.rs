@@ -0,0 +1,129 @@
+use algorithms::features::EdgeFeatures;
+use csr::generators::erdos_renyi_gnm;
+use csr::prelude::*;
+use graph::*;
+use hyperloglog_rs::prelude::*;
+use hypersketching::sketches::*;
+use hypersketching::*;
+use rayon::prelude::*;
+
+type SketchingWith<S> = HyperSketching<VecCSR<usize>, HyperSketchingData<Vec<S>>, Vec<S>, S>;
+
+#[test]
+fn test_retained_hops_match_the_counters_of_all_hops() {
+    let graph: VecCSR<usize> = erdos_renyi_gnm(200, 600, 23);
+    let mut all_hops =
+        SketchingWith::<HyperLogLog<Precision10, 6>>::new(4, false, graph.clone()).unwrap();
+    all_hops.fit();
+    let mut retained = SketchingWith::<HyperLogLog<Precision10, 6>>::new(4, false, graph).unwrap();
+    retained.set_retained_hops(&[1, 3]).unwrap();
+    retained.fit();
+
+    assert_eq!(retained.retained_hops(), &[1, 3]);
+    assert_eq!(retained.feature_len(), 8);
+    assert_eq!(
+        retained.feature_names(),
+        vec![
+            "overlap_h1_h1",
+            "overlap_h1_h3",
+            "overlap_h3_h1",
+            "overlap_h3_h3",
+            "left_diff_h1",
+            "left_diff_h3",
+            "right_diff_h1",
+            "right_diff_h3",
+        ]
+    );
+    assert_eq!(
+        retained.node_feature_names(),
+        vec!["cardinality_h1", "cardinality_h3"]
+    );
+    let all_hops_node_features = all_hops.node_features_matrix();
+    let retained_node_features = retained.node_features_matrix();
+    for (all_hops_row, retained_row) in all_hops_node_features
+        .chunks_exact(4)
+        .zip(retained_node_features.chunks_exact(2))
+    {
+        assert_eq!(retained_row, [all_hops_row[1], all_hops_row[3]]);
+    }
+}
+
+#[test]
+fn test_retained_hops_with_gaps_of_any_length() {
+    let graph: VecCSR<usize> = erdos_renyi_gnm(100, 250, 27);
+    let mut all_hops =
+        SketchingWith::<HyperLogLog<Precision10, 6>>::new(6, false, graph.clone()).unwrap();
+    all_hops.fit();
+    let all_hops_node_features = all_hops.node_features_matrix();
+
+    // Consecutive retained hops, and gaps of odd and even lengths.
+    for retained_hops in [
+        &[5][..],
+        &[4, 5][..],
+        &[0, 1, 5][..],
+        &[0, 2, 5][..],
+        &[1, 3, 4, 5][..],
+    ] {
+        let mut retained =
+            SketchingWith::<HyperLogLog<Precision10, 6>>::new(6, false, graph.clone()).unwrap();
+        retained.set_retained_hops(retained_hops).unwrap();
+        retained.fit();
+        let retained_node_features = retained.node_features_matrix();
+        for (all_hops_row, retained_row) in all_hops_node_features
+            .chunks_exact(6)
+            .zip(retained_node_features.chunks_exact(retained_hops.len()))
+        {
+            let expected: Vec<f32> = retained_hops.iter().map(|&hop| all_hops_row[hop]).collect();
+            assert_eq!(retained_row, expected);
+        }
+    }
+}
+
+#[test]
+fn test_retained_hops_features_match_exact_features() {
+    let graph: VecCSR<usize> = erdos_renyi_gnm(100, 200, 29);
+    let non_edges: Vec<(usize, usize)> = (0..100)
+        .map(|src| (src, (src * 31 + 7) % 100))
+        .filter(|&(src, dst)| src != dst && !graph.has_successor(src, dst))
+        .collect();
+
+    for normalize in [false, true] {
+        for retained_hops in [&[2][..], &[0, 2][..]] {
+            let mut sketching =
+                SketchingWith::<BottomK<128>>::new(3, normalize, graph.clone()).unwrap();
+            sketching.set_retained_hops(retained_hops).unwrap();
+            sketching.fit();
+
+            let exact = sketching
+                .exact_features()
+                .par_edge_features_matrix(non_edges.par_iter().copied());
+            // The small balls are sketched exactly by the bottom-k sketches.
+            assert_eq!(
+                sketching
+                    .biased_features::<false>()
+                    .par_edge_features_matrix(non_edges.par_iter().copied()),
+                exact
+            );
+            assert_eq!(
+                sketching
+                    .bias_aware_features::<false>()
+                    .par_edge_features_matrix(non_edges.par_iter().copied()),
+                exact
+            );
+        }
+    }
+}
+
+#[test]
+fn test_invalid_retained_hops() {
+    let graph: VecCSR<usize> = erdos_renyi_gnm(50, 100, 37);
+    let mut sketching =
+        SketchingWith::<HyperLogLog<Precision10, 6>>::new(3, false, graph.clone()).unwrap();
+    assert!(sketching.set_retained_hops(&[]).is_err());
+    assert!(sketching.set_retained_hops(&[0, 1]).is_err());
+    assert!(sketching.set_retained_hops(&[1, 1, 2]).is_err());
+
+    sketching.set_retained_hops(&[2]).unwrap();
+    sketching.fit();
+    assert!(sketching.insert_edges(graph, &[]).is_err());
+}
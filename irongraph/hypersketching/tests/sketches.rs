@@ -0,0 +1,83 @@
+use algorithms::features::EdgeFeatures;
+use csr::generators::erdos_renyi_gnm;
+use csr::prelude::*;
+use hypersketching::sketches::*;
+use hypersketching::*;
+use rayon::prelude::*;
+
+type SketchingWith<S> = HyperSketching<VecCSR<usize>, HyperSketchingData<Vec<S>>, Vec<S>, S>;
+
+#[test]
+fn test_bottom_k_is_exact_on_small_sets() {
+    let mut left = BottomK::<64>::default();
+    let mut right = BottomK::<64>::default();
+    (0..40).for_each(|element: u32| left.insert_element(element));
+    (20..50).for_each(|element: u32| right.insert_element(element));
+    assert!(left.is_exact());
+    assert_eq!(left.cardinality(), 40.0);
+    assert_eq!(left.intersection_cardinality(&right), 20.0);
+
+    left.union_assign(&right);
+    assert_eq!(left.cardinality(), 50.0);
+}
+
+#[test]
+fn test_element_hashes_are_stable() {
+    // The hashes of the nodes must not change across platforms and releases.
+    let mut sketch = BottomK::<4>::default();
+    sketch.insert_element(0_usize);
+    sketch.insert_element(1_u64);
+    assert_eq!(
+        sketch.hashes(),
+        &[0x0629_6716_8a4a_d94b, 0x657b_0edc_7f59_83b6]
+    );
+}
+
+#[test]
+fn test_min_hash_estimates() {
+    let mut left = MinHash::<256>::default();
+    let mut right = MinHash::<256>::default();
+    assert_eq!(left.cardinality(), 0.0);
+    (0..10_000).for_each(|element: u32| left.insert_element(element));
+    (5_000..20_000).for_each(|element: u32| right.insert_element(element));
+
+    assert!((left.cardinality() - 10_000.0).abs() < 2_000.0);
+    assert!((left.jaccard(&right) - 1.0 / 3.0).abs() < 0.1);
+    assert!((left.intersection_cardinality(&right) - 5_000.0).abs() < 1_500.0);
+    assert_eq!(left.jaccard(&left), 1.0);
+}
+
+#[test]
+fn test_hyper_sketching_with_bottom_k_matches_exact_features() {
+    let graph: VecCSR<usize> = erdos_renyi_gnm(100, 200, 31);
+    let edges: Vec<(usize, usize)> = (0..100).map(|src| (src, (src * 29 + 7) % 100)).collect();
+
+    for normalize in [false, true] {
+        // The balls have fewer nodes than the sketches keep, so they are exact.
+        let mut sketching =
+            SketchingWith::<BottomK<128>>::new(2, normalize, graph.clone()).unwrap();
+        sketching.fit();
+        assert_eq!((sketching.precision(), sketching.bits()), (128, 64));
+        assert_eq!(
+            sketching
+                .biased_features::<false>()
+                .par_edge_features_matrix(edges.par_iter().copied()),
+            sketching
+                .exact_features()
+                .par_edge_features_matrix(edges.par_iter().copied())
+        );
+    }
+}
+
+#[test]
+fn test_hyper_sketching_with_min_hash() {
+    let graph: VecCSR<usize> = erdos_renyi_gnm(300, 900, 32);
+    let edges: Vec<(usize, usize)> = (0..300).map(|src| (src, (src * 7 + 1) % 300)).collect();
+
+    let mut sketching = SketchingWith::<MinHash<128>>::new(2, false, graph).unwrap();
+    sketching.fit();
+    let report = sketching.accuracy_report(edges.par_iter().copied());
+    assert_eq!(report.precision(), 128);
+    assert!(report.mean_absolute_error().is_finite());
+    assert!(report.mean_absolute_error() < 10.0, "{}", report);
+}
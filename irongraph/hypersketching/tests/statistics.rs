@@ -0,0 +1,57 @@
+use csr::generators::path;
+use csr::prelude::*;
+use hypersketching::sketches::*;
+use hypersketching::statistics::GraphStatistics;
+use hypersketching::*;
+
+type Sketching = HyperSketching<
+    VecCSR<usize>,
+    HyperSketchingData<Vec<BottomK<128>>>,
+    Vec<BottomK<128>>,
+    BottomK<128>,
+>;
+
+fn assert_close(left: &[f64], right: &[f64]) {
+    assert_eq!(left.len(), right.len());
+    for (left, right) in left.iter().zip(right) {
+        assert!((left - right).abs() < 1e-9, "{:?} != {:?}", left, right);
+    }
+}
+
+#[test]
+fn test_statistics_of_a_path() {
+    let graph: VecCSR<usize> = path(5);
+    let mut sketching = Sketching::new(4, false, graph).unwrap();
+    sketching.fit();
+    let statistics = sketching.statistics().unwrap();
+
+    // The 20 ordered pairs of distinct nodes are 8 at distance 1, 6 at
+    // distance 2, 4 at distance 3 and 2 at distance 4.
+    assert_eq!(statistics.number_of_nodes(), 5);
+    assert_eq!(statistics.number_of_hops(), 4);
+    assert_close(
+        statistics.neighbourhood_function(),
+        &[5.0, 13.0, 19.0, 23.0, 25.0],
+    );
+    assert_close(statistics.distance_distribution(), &[0.4, 0.3, 0.2, 0.1]);
+    assert_close(statistics.mean_reachable_set_sizes(), &[2.6, 3.8, 4.6, 5.0]);
+    assert_close(&[statistics.average_distance()], &[2.0]);
+    assert_close(&[statistics.spid()], &[0.5]);
+
+    let json = serde_json::to_string(&statistics).unwrap();
+    assert_eq!(
+        serde_json::from_str::<GraphStatistics>(&json).unwrap(),
+        statistics
+    );
+}
+
+#[test]
+fn test_statistics_errors() {
+    let graph: VecCSR<usize> = path(5);
+    let mut sketching = Sketching::new(3, false, graph).unwrap();
+    assert!(sketching.statistics().is_err());
+
+    sketching.set_retained_hops(&[0, 2]).unwrap();
+    sketching.fit();
+    assert!(sketching.statistics().is_err());
+}
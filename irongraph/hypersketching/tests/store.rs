@@ -0,0 +1,59 @@
+use algorithms::features::EdgeFeatures;
+use csr::generators::erdos_renyi_gnm;
+use csr::prelude::*;
+use hyperloglog_rs::prelude::*;
+use hypersketching::*;
+use rayon::prelude::*;
+
+type Counters = Vec<HyperLogLog<Precision12, 6>>;
+type Sketching = HyperSketching<VecCSR<usize>, HyperSketchingData<Counters>, Counters>;
+
+#[test]
+fn test_store_and_load_with_graph_hash() {
+    let graph: VecCSR<usize> = erdos_renyi_gnm(200, 800, 7);
+    let other: VecCSR<usize> = erdos_renyi_gnm(200, 800, 8);
+    let edges: Vec<(usize, usize)> = (0..100).map(|src| (src, 199 - src)).collect();
+
+    let mut sketching = Sketching::new(2, false, graph.clone()).unwrap();
+    assert_eq!(sketching.graph_hash(), None);
+    sketching.fit();
+    assert!(sketching.graph_hash().is_some());
+    let features = sketching
+        .biased_features::<false>()
+        .par_edge_features_matrix(edges.par_iter().copied());
+
+    let path = std::env::temp_dir().join("hypersketching_store.hs");
+    sketching.store(&path).unwrap();
+
+    let loaded = unsafe { Sketching::load(&path, graph, true) }.unwrap();
+    assert_eq!(loaded.graph_hash(), sketching.graph_hash());
+    assert_eq!(loaded.number_of_hops(), 2);
+    assert_eq!(
+        loaded
+            .biased_features::<false>()
+            .par_edge_features_matrix(edges.par_iter().copied()),
+        features
+    );
+
+    // The sketches cannot be reused on a different graph, unless the check is skipped.
+    let error = unsafe { Sketching::load(&path, other.clone(), true) }
+        .err()
+        .unwrap();
+    assert!(error.to_string().contains("different graph"));
+    assert!(unsafe { Sketching::load(&path, other, false) }.is_ok());
+}
+
+#[test]
+fn test_graph_hash_is_stable() {
+    // The hash of the path 0 -> 1 -> 2 must not change across platforms and releases.
+    let edges: Vec<(usize, usize)> = vec![(0, 1), (1, 2)];
+    let graph: VecCSR<usize> = CSRBuilder::default()
+        .number_of_edges(edges.len())
+        .number_of_nodes(3)
+        .sorted()
+        .build(edges.iter().copied());
+
+    let mut sketching = Sketching::new(1, false, graph).unwrap();
+    sketching.fit();
+    assert_eq!(sketching.graph_hash(), Some(0x7a0e73d8c04f8644));
+}